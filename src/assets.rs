@@ -0,0 +1,56 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use sdl2::{
+    image::LoadTexture,
+    render::{Texture, TextureCreator},
+    ttf::{Font, Sdl2TtfContext},
+    video::WindowContext,
+};
+
+//loads every image file only once, animations share the texture through an Rc
+pub struct TextureCache<'a> {
+    loader: &'a TextureCreator<WindowContext>,
+    textures: RefCell<HashMap<String, Rc<Texture<'a>>>>, //path -> texture
+}
+impl<'a> TextureCache<'a> {
+    pub fn new(loader: &'a TextureCreator<WindowContext>) -> Self {
+        TextureCache { loader, textures: RefCell::new(HashMap::new()) }
+    }
+    pub fn load(&self, path: &str) -> Result<Rc<Texture<'a>>, String> {
+        if let Some(texture) = self.textures.borrow().get(path) {
+            return Ok(Rc::clone(texture))
+        }
+        let texture = Rc::new(self.loader.load_texture(path).map_err(|e| format!("{}: {}", path, e))?);
+        self.textures.borrow_mut().insert(path.to_string(), Rc::clone(&texture));
+        Ok(texture)
+    }
+}
+
+//loads every font only once per size, texts share it through an Rc
+pub struct FontCache<'a> {
+    ttf: &'a Sdl2TtfContext,
+    loader: &'a TextureCreator<WindowContext>,
+    fonts: RefCell<HashMap<(String, u16, u16), Rc<Font<'a, 'static>>>>, //(path, size, outline) -> font
+}
+impl<'a> FontCache<'a> {
+    pub fn new(ttf: &'a Sdl2TtfContext, loader: &'a TextureCreator<WindowContext>) -> Self {
+        FontCache { ttf, loader, fonts: RefCell::new(HashMap::new()) }
+    }
+    pub fn load(&self, path: &str, size: u16) -> Result<Rc<Font<'a, 'static>>, String> {
+        self.outlined(path, size, 0)
+    }
+    //the same font only drawing the outlines of the glyphs, `thickness` pixels wide
+    pub fn outlined(&self, path: &str, size: u16, thickness: u16) -> Result<Rc<Font<'a, 'static>>, String> {
+        let key = (path.to_string(), size, thickness);
+        if let Some(font) = self.fonts.borrow().get(&key) {
+            return Ok(Rc::clone(font))
+        }
+        let mut font = self.ttf.load_font(path, size).map_err(|e| format!("{}: {}", path, e))?;
+        font.set_outline_width(thickness);
+        let font = Rc::new(font);
+        self.fonts.borrow_mut().insert(key, Rc::clone(&font));
+        Ok(font)
+    }
+    pub fn loader(&self) -> &'a TextureCreator<WindowContext> {
+        self.loader
+    }
+}
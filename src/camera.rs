@@ -0,0 +1,160 @@
+use std::time::Duration;
+use sdl2::rect::{Point, Rect};
+
+use crate::{random, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+//how far the camera can zoom out and in
+const ZOOM_RANGE: (f32, f32) = (0.5, 3.0);
+
+//the part of the world that is on the screen
+pub struct Camera {
+    x: f32, //the world point in the middle of the screen
+    y: f32,
+    prev_x: f32, //x before the current tick, used for interpolating
+    prev_y: f32,
+    alpha: f32, //how far rendering is between prev_x/prev_y and x/y
+    pub deadzone: (u32, u32), //the target can move this many screen pixels around the middle before the camera follows
+    pub smoothing: f32, //how fast the camera catches up with the target, higher is faster
+    pub bounds: Option<Rect>, //the camera never shows anything outside of this
+    zoom: f32, //2.0 shows everything twice as big
+    prev_zoom: f32,
+    target_zoom: f32, //the zoom the camera is easing towards, with the same smoothing as the movement
+    screen: (u32, u32), //the logical size of the screen, see Display
+    shake: Duration, //time until the screen stops shaking
+    shake_length: Duration, //the length of the current shake, it gets weaker towards the end
+    shake_strength: f32, //the largest offset of the shake in pixels
+    jitter: (i32, i32), //the offset of the shake in the current tick
+}
+impl Camera {
+    pub fn new() -> Self {
+        Camera {
+            x: 0.0,
+            y: 0.0,
+            prev_x: 0.0,
+            prev_y: 0.0,
+            alpha: 1.0,
+            deadzone: (0, 0),
+            smoothing: f32::INFINITY, //no smoothing
+            bounds: None,
+            zoom: 1.0,
+            prev_zoom: 1.0,
+            target_zoom: 1.0,
+            screen: (SCREEN_WIDTH, SCREEN_HEIGHT),
+            shake: Duration::ZERO,
+            shake_length: Duration::ZERO,
+            shake_strength: 0.0,
+            jitter: (0, 0),
+        }
+    }
+    //the size of the visible area in world pixels at a zoom
+    fn size(&self, zoom: f32) -> (f32, f32) {
+        (self.screen.0 as f32 / zoom, self.screen.1 as f32 / zoom)
+    }
+    //zooming gradually, the zoom is kept within ZOOM_RANGE
+    pub fn zoom_to(&mut self, zoom: f32) {
+        self.target_zoom = zoom.clamp(ZOOM_RANGE.0, ZOOM_RANGE.1);
+    }
+    //the zoom between the last two ticks, for drawing
+    pub fn zoom(&self) -> f32 {
+        self.prev_zoom + (self.zoom - self.prev_zoom) * self.alpha
+    }
+    //following the screen's size when the window changes
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen = (width, height);
+        self.clamp();
+        self.snapshot();
+    }
+    //jumping to a point without following, e.g. when the level starts
+    pub fn snap(&mut self, target: Point) {
+        self.x = target.x() as f32;
+        self.y = target.y() as f32;
+        self.clamp();
+        self.snapshot();
+    }
+    //moving towards `target` if it left the deadzone, once per tick
+    pub fn follow(&mut self, target: Point, dt: Duration) {
+        //covering the same part of the distance every second, so it slows down when getting close
+        let t = if self.smoothing.is_finite() {1.0 - (-self.smoothing * dt.as_secs_f32()).exp()} else {1.0};
+        self.zoom += (self.target_zoom - self.zoom) * t;
+        //the deadzone stays the same size on the screen whatever the zoom
+        let (half_w, half_h) = (self.deadzone.0 as f32 / 2.0 / self.zoom, self.deadzone.1 as f32 / 2.0 / self.zoom);
+        let (dx, dy) = (target.x() as f32 - self.x, target.y() as f32 - self.y);
+        let goal_x = self.x + dx - dx.clamp(-half_w, half_w);
+        let goal_y = self.y + dy - dy.clamp(-half_h, half_h);
+        self.x += (goal_x - self.x) * t;
+        self.y += (goal_y - self.y) * t;
+        self.clamp();
+
+        self.shake = self.shake.saturating_sub(dt);
+        let strength = self.shaking() as i32;
+        self.jitter = if strength > 0 {
+            (random(-strength, strength + 1), random(-strength, strength + 1))
+        } else {
+            (0, 0)
+        };
+    }
+    fn clamp(&mut self) {
+        let Some(bounds) = self.bounds else {return};
+        let (w, h) = self.size(self.zoom);
+        //a map smaller than the screen stays in the middle
+        self.x = if bounds.width() as f32 <= w {
+            bounds.center().x() as f32
+        } else {
+            self.x.clamp(bounds.left() as f32 + w / 2.0, bounds.right() as f32 - w / 2.0)
+        };
+        self.y = if bounds.height() as f32 <= h {
+            bounds.center().y() as f32
+        } else {
+            self.y.clamp(bounds.top() as f32 + h / 2.0, bounds.bottom() as f32 - h / 2.0)
+        };
+    }
+    //the strength of the current shake
+    fn shaking(&self) -> f32 {
+        if self.shake.is_zero() {
+            return 0.0
+        }
+        self.shake_strength * self.shake.as_secs_f32() / self.shake_length.as_secs_f32()
+    }
+    //shaking the screen, a weaker shake doesn't cut a stronger one short
+    pub fn shake(&mut self, strength: f32, length: Duration) {
+        if strength >= self.shaking() {
+            self.shake_strength = strength;
+            self.shake = length;
+            self.shake_length = length;
+        }
+    }
+    //remembering where the camera was before a tick moves it
+    pub fn snapshot(&mut self) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+        self.prev_zoom = self.zoom;
+    }
+    pub fn interpolate(&mut self, alpha: f32) {
+        self.alpha = alpha.clamp(0.0, 1.0);
+    }
+    //a value between its last two ticks, for things that move with the camera
+    pub fn between(&self, prev: i32, current: i32) -> i32 {
+        prev + ((current - prev) as f32 * self.alpha).round() as i32
+    }
+    fn position(&self) -> (f32, f32) {
+        (
+            self.prev_x + (self.x - self.prev_x) * self.alpha,
+            self.prev_y + (self.y - self.prev_y) * self.alpha,
+        )
+    }
+    //what has to be added to world coordinates to get screen coordinates (before zooming)
+    pub fn offset(&self) -> (i32, i32) {
+        let (x, y) = self.position();
+        let zoom = self.zoom();
+        let (w, h) = self.size(zoom);
+        //the shake is as strong on the screen whatever the zoom
+        let (jitter_x, jitter_y) = (self.jitter.0 as f32 / zoom, self.jitter.1 as f32 / zoom);
+        ((w / 2.0 - x + jitter_x).round() as i32, (h / 2.0 - y + jitter_y).round() as i32)
+    }
+    //the visible area in world coordinates
+    pub fn view(&self) -> Rect {
+        let (x, y) = self.position();
+        let (w, h) = self.size(self.zoom());
+        Rect::new((x - w / 2.0).round() as i32, (y - h / 2.0).round() as i32, w.ceil() as u32, h.ceil() as u32)
+    }
+}
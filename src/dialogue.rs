@@ -0,0 +1,239 @@
+use std::{collections::{HashMap, HashSet}, fs, rc::Rc, time::Duration};
+use sdl2::{pixels::Color, rect::{Point, Rect}, render::{BlendMode, WindowCanvas}};
+use serde::Deserialize;
+
+use crate::{
+    assets::{FontCache, TextureCache}, display::Display, input::{Action, Controls}, inventory::Inventory, text::Text,
+};
+
+//what happened so far in the game, set and checked by conversations
+pub type Flags = HashSet<String>;
+
+//characters revealed per second
+const REVEAL_SPEED: f32 = 40.0;
+
+//one conversation read from a file, see assets/dialogue
+#[derive(Deserialize)]
+pub struct Script {
+    start: String, //the key of the first line
+    lines: HashMap<String, Line>,
+}
+#[derive(Deserialize)]
+struct Line {
+    speaker: Option<String>,
+    portrait: Option<String>, //the path to an image of the speaker
+    portrait_src: Option<(i32, i32, u32, u32)>, //the part of the image to show, all of it by default
+    text: String,
+    next: Option<String>, //the line after this one if there are no replies, the conversation ends without one
+    #[serde(default)]
+    replies: Vec<Reply>,
+    #[serde(default)]
+    requires: Vec<String>, //conditions, see `holds`
+    otherwise: Option<String>, //the line said instead if a condition doesn't hold
+    #[serde(default)]
+    set: Vec<String>, //flags set when the line is said
+    #[serde(default)]
+    give: Vec<String>, //items given to the character when the line is said
+    #[serde(default)]
+    take: Vec<String>, //items taken from the character when the line is said
+}
+#[derive(Deserialize)]
+struct Reply {
+    text: String,
+    next: Option<String>,
+    #[serde(default)]
+    requires: Vec<String>, //the reply is only offered if these hold
+    #[serde(default)]
+    set: Vec<String>,
+}
+impl Script {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let script: Script = toml::from_str(&content).map_err(|e| format!("{}: {}", path, e))?;
+        script.validate().map_err(|e| format!("{}: {}", path, e))?;
+        Ok(script)
+    }
+    //every line a conversation can go to has to exist
+    fn validate(&self) -> Result<(), String> {
+        let exists = |key: &Option<String>| match key {
+            Some(key) if !self.lines.contains_key(key) => Err(format!("there is no line '{}'", key)),
+            _ => Ok(())
+        };
+        exists(&Some(self.start.clone()))?;
+        for line in self.lines.values() {
+            exists(&line.next)?;
+            exists(&line.otherwise)?;
+            for reply in &line.replies {
+                exists(&reply.next)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//"flag", "!flag", "item:name" or "!item:name"
+fn holds(condition: &str, flags: &Flags, inventory: &Inventory) -> bool {
+    if let Some(condition) = condition.strip_prefix('!') {
+        return !holds(condition, flags, inventory)
+    }
+    match condition.strip_prefix("item:") {
+        Some(item) => inventory.count(item) > 0,
+        None => flags.contains(condition),
+    }
+}
+
+//a conversation going on
+pub struct Conversation {
+    script: Rc<Script>, //loaded once with the level, shared by every conversation using it
+    line: String, //the key of the current line
+    shown: f32, //how many characters of the text are revealed
+    replies: Vec<usize>, //the current line's replies whose conditions hold
+    selected: usize, //the index in `replies`
+}
+impl Conversation {
+    //None if there is nothing to say, e.g. the first line's condition doesn't hold and it has no `otherwise`
+    pub fn start(script: Rc<Script>, flags: &mut Flags, inventory: &mut Inventory) -> Option<Self> {
+        let start = Some(script.start.clone());
+        let mut conversation = Conversation { script, line: String::new(), shown: 0.0, replies: vec![], selected: 0 };
+        conversation.enter(start, flags, inventory).then_some(conversation)
+    }
+    //going to a line, or to its `otherwise` if it can't be said, false if the conversation is over
+    fn enter(&mut self, mut key: Option<String>, flags: &mut Flags, inventory: &mut Inventory) -> bool {
+        //`otherwise` can go around in a circle, but there is no point in trying more lines than there are
+        for _ in 0..=self.script.lines.len() {
+            let Some(current) = key else {return false};
+            let line = &self.script.lines[&current];
+            if !line.requires.iter().all(|condition| holds(condition, flags, inventory)) {
+                key = line.otherwise.clone();
+                continue
+            }
+            flags.extend(line.set.iter().cloned());
+            for item in &line.give {
+                inventory.add(item, 1);
+            }
+            for item in &line.take {
+                inventory.take(item, 1);
+            }
+            self.replies = line.replies.iter()
+                .enumerate()
+                .filter(|(_, reply)| reply.requires.iter().all(|condition| holds(condition, flags, inventory)))
+                .map(|(i, _)| i)
+                .collect();
+            self.line = current;
+            self.shown = 0.0;
+            self.selected = 0;
+            return true
+        }
+        false
+    }
+    fn current(&self) -> &Line {
+        &self.script.lines[&self.line]
+    }
+    //revealing the text and following the player's choices, false when the conversation is over
+    pub fn update(&mut self, controls: &Controls, flags: &mut Flags, inventory: &mut Inventory, dt: Duration) -> bool {
+        let length = self.current().text.chars().count() as f32;
+        self.shown = (self.shown + REVEAL_SPEED * dt.as_secs_f32()).min(length);
+        let count = self.replies.len();
+        if count > 0 {
+            if controls.pressed(Action::MoveUp) {
+                self.selected = (self.selected + count - 1) % count;
+            }
+            if controls.pressed(Action::MoveDown) {
+                self.selected = (self.selected + 1) % count;
+            }
+        }
+        if !controls.pressed(Action::Interact) {
+            return true
+        }
+        //the first press shows the whole text, the next one goes on
+        if self.shown < length {
+            self.shown = length;
+            return true
+        }
+        let next = match self.replies.get(self.selected) {
+            Some(&i) => {
+                let reply = &self.current().replies[i];
+                flags.extend(reply.set.iter().cloned());
+                reply.next.clone()
+            }
+            None => self.current().next.clone(),
+        };
+        self.enter(next, flags, inventory)
+    }
+    //the replies are only shown once the text is
+    fn revealed(&self) -> bool {
+        self.shown as usize >= self.current().text.chars().count()
+    }
+}
+
+//draws the current line of a conversation in a box at the bottom of the screen
+pub struct DialogueBox<'a> {
+    name: Text<'a>, //the speaker's name
+    body: Text<'a>,
+    replies: Text<'a>, //one reply per row, the selected one highlighted
+}
+impl<'a> DialogueBox<'a> {
+    pub fn new(fonts: &FontCache<'a>) -> Result<Self, String> {
+        let origin = Point::new(0, 0);
+        Ok(DialogueBox {
+            name: Text::new(fonts, "assets/fonts/Aiden-v7DO.otf", 44, Color::RGB(255, 220, 120), origin)?,
+            body: Text::new(fonts, "assets/fonts/Newretrostyle.ttf", 36, Color::RGB(255, 255, 255), origin)?,
+            replies: Text::new(fonts, "assets/fonts/Newretrostyle.ttf", 36, Color::RGB(180, 180, 180), origin)?,
+        })
+    }
+    pub fn present(&mut self, canvas: &mut WindowCanvas, conversation: &Conversation, display: &Display, textures: &TextureCache<'a>) -> Result<(), String> {
+        let line = conversation.current();
+        let (margin, padding, portrait_size) = (60, 30, 220);
+        let left = margin + padding + if line.portrait.is_some() {portrait_size as i32 + padding} else {0};
+        let width = (display.width as i32 - margin - padding - left).max(1) as u32;
+
+        //the whole text is laid out from the start so the words don't jump between rows while it's revealed
+        self.body.wrap(Some(width))?;
+        self.body.set(&line.text)?;
+        self.body.set_visible(Some(conversation.shown as usize))?;
+        self.name.set(line.speaker.as_deref().unwrap_or(""))?;
+
+        let (mut rows, mut spans) = (vec![], vec![]);
+        if conversation.revealed() {
+            let mut at = 0;
+            for (i, &reply) in conversation.replies.iter().enumerate() {
+                let marker = if i == conversation.selected {"> "} else {"  "};
+                let row = format!("{}{}", marker, line.replies[reply].text);
+                let length = row.chars().count();
+                if i == conversation.selected {
+                    spans.push((at..at + length, Color::RGB(255, 220, 120)));
+                }
+                at += length + 1;
+                rows.push(row);
+            }
+        }
+        self.replies.set(&rows.join("\n"))?;
+        self.replies.set_spans(spans)?;
+
+        //growing upwards with the text, but never smaller than the portrait
+        let mut text_h = 80 + self.body.dst.height() as i32;
+        if !rows.is_empty() {
+            text_h += self.replies.dst.height() as i32;
+        }
+        let h = text_h.max(portrait_size as i32 + 2 * padding) + padding;
+        let area = Rect::new(margin, display.height as i32 - margin / 2 - h, display.width - 2 * margin as u32, h as u32);
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, 200));
+        canvas.fill_rect(area)?;
+        canvas.set_blend_mode(BlendMode::None);
+        canvas.set_draw_color(Color::RGB(255, 255, 255));
+        canvas.draw_rect(area)?;
+
+        if let Some(path) = &line.portrait {
+            let portrait = textures.load(path)?;
+            let src = line.portrait_src.map(|(x, y, w, h)| Rect::new(x, y, w, h));
+            canvas.copy(&portrait, src, Rect::new(area.x() + padding, area.y() + padding, portrait_size, portrait_size))?;
+        }
+        self.name.dst.reposition((left, area.y() + 15));
+        self.name.present(canvas)?;
+        self.body.dst.reposition((left, area.y() + 80));
+        self.body.present(canvas)?;
+        self.replies.dst.reposition((left, self.body.dst.bottom()));
+        self.replies.present(canvas)
+    }
+}
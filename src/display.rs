@@ -0,0 +1,86 @@
+use sdl2::{rect::Point, render::WindowCanvas, video::FullscreenType};
+use serde::{Deserialize, Serialize};
+
+use crate::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowMode {
+    Windowed,
+    Borderless, //a window covering the whole screen
+    Fullscreen, //changes the monitor's resolution
+}
+impl WindowMode {
+    pub const ALL: [WindowMode; 3] = [WindowMode::Windowed, WindowMode::Borderless, WindowMode::Fullscreen];
+    //F11 goes through the modes in this order
+    pub fn next(self) -> Self {
+        match self {
+            WindowMode::Windowed => WindowMode::Borderless,
+            WindowMode::Borderless => WindowMode::Fullscreen,
+            WindowMode::Fullscreen => WindowMode::Windowed,
+        }
+    }
+}
+
+//the logical screen everything is drawn to, scaled to the window with black bars where it doesn't fit
+pub struct Display {
+    pub width: u32, //the logical size, always SCREEN_HEIGHT tall and as wide as the window's aspect ratio
+    pub height: u32,
+    pub mode: WindowMode,
+    pub outdated: bool, //the window settings changed, main applies them since it has the canvas
+    scale: f32, //window pixels per logical pixel
+    bars: (f32, f32), //the size of the black bars on the left and the top in window pixels
+}
+impl Display {
+    pub fn new() -> Self {
+        Display { width: SCREEN_WIDTH, height: SCREEN_HEIGHT, mode: WindowMode::Windowed, outdated: false, scale: 1.0, bars: (0.0, 0.0) }
+    }
+    //adapting the logical size to the window, after it was created or resized
+    pub fn fit(&mut self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        //window size rather than output size, so it matches the mouse's coordinates
+        let (window_w, window_h) = canvas.window().size();
+        //anything between 4:3 and 21:9 fills the window, wider or narrower windows get bars
+        let aspect = (window_w as f32 / window_h.max(1) as f32).clamp(4.0 / 3.0, 21.0 / 9.0);
+        self.height = SCREEN_HEIGHT;
+        self.width = (SCREEN_HEIGHT as f32 * aspect).round() as u32;
+        canvas.set_logical_size(self.width, self.height).map_err(|e| e.to_string())?;
+
+        //the same letterboxing SDL does with a logical size
+        self.scale = (window_w as f32 / self.width as f32).min(window_h as f32 / self.height as f32);
+        self.bars = (
+            (window_w as f32 - self.width as f32 * self.scale) / 2.0,
+            (window_h as f32 - self.height as f32 * self.scale) / 2.0,
+        );
+        Ok(())
+    }
+    //switching to `mode`, `size` is the window's size when windowed and the monitor's resolution in fullscreen
+    pub fn apply(&mut self, canvas: &mut WindowCanvas, mode: WindowMode, (w, h): (u32, u32)) -> Result<(), String> {
+        //leaving fullscreen first, so the new size doesn't change the monitor's resolution
+        if mode == WindowMode::Windowed {
+            self.set_mode(canvas, mode)?;
+        }
+        canvas.window_mut().set_size(w, h).map_err(|e| e.to_string())?;
+        self.set_mode(canvas, mode)
+    }
+    fn set_mode(&mut self, canvas: &mut WindowCanvas, mode: WindowMode) -> Result<(), String> {
+        let fullscreen = match mode {
+            WindowMode::Windowed => FullscreenType::Off,
+            WindowMode::Borderless => FullscreenType::Desktop,
+            WindowMode::Fullscreen => FullscreenType::True,
+        };
+        canvas.window_mut().set_fullscreen(fullscreen)?;
+        self.mode = mode;
+        self.fit(canvas)
+    }
+    //a point of the window (e.g. the mouse) in logical coordinates
+    pub fn to_logical(&self, x: i32, y: i32) -> Point {
+        Point::new(
+            ((x as f32 - self.bars.0) / self.scale).floor() as i32,
+            ((y as f32 - self.bars.1) / self.scale).floor() as i32,
+        )
+    }
+    //the top left corner of a w x h rect in the middle of the screen
+    pub fn center(&self, w: u32, h: u32) -> Point {
+        Point::new((self.width as i32 - w as i32) / 2, (self.height as i32 - h as i32) / 2)
+    }
+}
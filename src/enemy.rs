@@ -0,0 +1,158 @@
+use std::time::Duration;
+use sdl2::{rect::Rect, render::WindowCanvas};
+use serde::Deserialize;
+
+use crate::{chance, health::Health, nav::Navigator, sweep, timing::step, Anim, Animal, Character, Condition, Direction, Entity, Layer, Presentable, World};
+
+//the numbers that make one kind of enemy different from another, see [enemies] in assets/animations.toml
+#[derive(Deserialize, Clone)]
+pub struct EnemyStats {
+    pub entity: String, //the name of the entity in the manifest
+    pub hp: u32,
+    pub speed: i32, //pixels per second while patrolling
+    pub chase_speed: i32, //pixels per second while chasing the player
+    pub sight: i32, //the player is chased within this many pixels
+    pub damage: u32, //dealt to the player on contact
+    pub cooldown: u64, //milliseconds between two contact hits
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum EnemyState {
+    Idle, //standing around for a while
+    Patrol, //wandering randomly
+    Chase, //walking towards the player
+    Attack, //touching the player
+    Dead,
+}
+
+pub struct Enemy<'a> {
+    body: Animal<'a>, //movement, collision and health are the same as an animal's
+    stats: EnemyStats,
+    state: EnemyState,
+    idle: Duration, //how long the enemy keeps idling
+    cooldown: Duration, //time until the next contact hit
+    navigator: Navigator, //the way to the player around obstacles while chasing
+}
+impl<'a> Enemy<'a> {
+    pub fn from(stats: EnemyStats, entity: Entity<'a>) -> Self {
+        let mut body = Animal::from(stats.speed, entity);
+        body.health = Health::new(stats.hp, Duration::from_millis(400));
+        Enemy { body, stats, state: EnemyState::Idle, idle: Duration::from_secs(1), cooldown: Duration::ZERO, navigator: Navigator::new(Duration::from_millis(500)) }
+    }
+    //the vector from the enemy to the player
+    fn to_player(&self, char: &Character) -> (i32, i32) {
+        let own = self.body.entity.hitbox.center();
+        let player = char.rep.hitbox.center();
+        (player.x() - own.x(), player.y() - own.y())
+    }
+    //the direction towards the player around obstacles, None if the grid knows no way
+    fn to_player_around(&mut self, char: &Character, world: &World, dt: Duration) -> Option<Direction> {
+        let own = self.body.entity.hitbox.center();
+        let player = char.rep.hitbox.center();
+        self.navigator.direction(&world.nav, own, player, self.body.step, dt)
+    }
+    //whether the enemy is up against the player in the direction it faces
+    fn touches(&self, char: &Character) -> bool {
+        //the player might step towards the enemy in the same tick
+        sweep(self.body.entity.hitbox, self.body.dir, 1 + char.step).has_intersection(char.rep.hitbox)
+    }
+    //the state the enemy should be in, based on where the player is
+    fn think(&mut self, char: &Character) {
+        let (dx, dy) = self.to_player(char);
+        let in_sight = dx * dx + dy * dy <= self.stats.sight * self.stats.sight && !char.health.is_dead();
+        if in_sight {
+            //facing the player along the longer axis
+            self.body.dir = if dx.abs() > dy.abs() {
+                if dx > 0 {Direction::Right} else {Direction::Left}
+            } else {
+                if dy > 0 {Direction::Down} else {Direction::Up}
+            };
+            self.state = if self.touches(char) {EnemyState::Attack} else {EnemyState::Chase};
+            return
+        }
+        match self.state {
+            EnemyState::Idle if self.idle.is_zero() => self.state = EnemyState::Patrol,
+            EnemyState::Idle => {}
+            //stopping now and then while patrolling
+            EnemyState::Patrol if chance(2) => {
+                self.state = EnemyState::Idle;
+                self.idle = Duration::from_secs(2);
+            }
+            EnemyState::Patrol => {}
+            _ => self.state = EnemyState::Patrol
+        }
+    }
+}
+impl Presentable for Enemy<'_> {
+    fn present(&self, canvas: &mut WindowCanvas, world_x: i32, world_y: i32) -> Result<(), String> {
+        self.body.present(canvas, world_x, world_y)
+    }
+    fn get_hitbox(&self) -> Rect {
+        self.body.get_hitbox()
+    }
+    fn get_body(&self) -> Rect {
+        self.body.get_body()
+    }
+    fn get_layer(&self) -> Layer {
+        self.body.get_layer()
+    }
+    fn behave(&mut self, char: &mut Character, world: &World, dt: Duration) {
+        self.cooldown = self.cooldown.saturating_sub(dt);
+        self.idle = self.idle.saturating_sub(dt);
+        //dying even while stunned, the enemy is removed once the animation is over
+        if self.body.health.is_dead() {
+            if self.state != EnemyState::Dead && self.body.entity.has(Anim::Death) {
+                self.body.entity.force_play(Anim::Death).unwrap();
+            }
+            self.state = EnemyState::Dead;
+            self.body.health.next(dt);
+            self.body.entity.next(dt);
+            return
+        }
+        if self.body.recover(dt) {
+            return
+        }
+        self.think(char);
+        match self.state {
+            EnemyState::Idle | EnemyState::Dead => {}
+            EnemyState::Patrol => {
+                self.body.step = step(self.stats.speed, dt);
+                self.body.decide(98);
+                if self.body.can_move(char, world) {
+                    self.body.move_dir();
+                }
+            }
+            EnemyState::Chase => {
+                self.body.step = step(self.stats.chase_speed, dt);
+                //walking straight at the player if there is no path
+                if let Some(dir) = self.to_player_around(char, world, dt) {
+                    self.body.dir = dir;
+                }
+                if self.body.can_move(char, world) {
+                    self.body.move_dir();
+                }
+            }
+            EnemyState::Attack => {
+                if self.cooldown.is_zero() && char.health.damage(self.stats.damage) > 0 {
+                    self.cooldown = Duration::from_millis(self.stats.cooldown);
+                }
+            }
+        }
+        if self.state != EnemyState::Idle {
+            self.body.entity.next(dt);
+        }
+    }
+    fn hurt(&mut self, damage: u32) -> bool {
+        self.body.hurt(damage)
+    }
+    fn push(&mut self, dx: i32, dy: i32) {
+        self.body.push(dx, dy)
+    }
+    fn condition(&self) -> Condition {
+        match self.state {
+            EnemyState::Dead if !self.body.entity.is_playing(Anim::Death) => Condition::Dead,
+            _ if self.body.health.is_dead() => Condition::Dying,
+            _ => self.body.condition()
+        }
+    }
+}
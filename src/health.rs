@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+pub struct Health {
+    pub hp: u32, //the current hit points
+    pub max: u32,
+    iframes: Duration, //how long the owner can't be hurt again after a hit
+    invulnerable: Duration, //the remaining time of the current invulnerability
+}
+impl Health {
+    pub fn new(max: u32, iframes: Duration) -> Self {
+        Health { hp: max, max, iframes, invulnerable: Duration::ZERO }
+    }
+    //returns the damage actually taken, nothing while invulnerable or dead
+    pub fn damage(&mut self, amount: u32) -> u32 {
+        if self.is_invulnerable() || self.is_dead() {
+            return 0
+        }
+        let taken = amount.min(self.hp);
+        self.hp -= taken;
+        self.invulnerable = self.iframes;
+        taken
+    }
+    //the dead can't be healed
+    pub fn heal(&mut self, amount: u32) {
+        if !self.is_dead() {
+            self.hp = (self.hp + amount).min(self.max);
+        }
+    }
+    pub fn next(&mut self, dt: Duration) {
+        self.invulnerable = self.invulnerable.saturating_sub(dt);
+    }
+    pub fn is_dead(&self) -> bool {
+        self.hp == 0
+    }
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerable.is_zero()
+    }
+    //blinking every 100ms while invulnerable
+    pub fn visible(&self) -> bool {
+        !self.is_invulnerable() || (self.invulnerable.as_millis() / 100) % 2 == 0
+    }
+}
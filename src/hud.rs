@@ -0,0 +1,78 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, rect::Point, render::WindowCanvas};
+
+use crate::{assets::FontCache, display::Display, health::Health, text::{Align, Text}, Entity, Presentable};
+
+//a row of hearts, one per hit point; a lost heart plays its animation and then disappears
+pub struct Hearts<'a> {
+    hearts: Vec<Entity<'a>>,
+    hp: u32, //the hit points the hearts were last synced to
+}
+impl<'a> Hearts<'a> {
+    //`heart` is the leftmost heart, the rest are placed next to it
+    pub fn new(heart: Entity<'a>, health: &Health) -> Self {
+        let mut hearts = vec![];
+        for i in 0..health.max {
+            let mut copy = heart.clone();
+            copy.x += (i * heart.w) as i32;
+            copy.dst();
+            hearts.push(copy);
+        }
+        Hearts { hearts, hp: health.hp }
+    }
+    pub fn sync(&mut self, health: &Health) -> Result<(), String> {
+        while self.hp > health.hp {
+            self.hp -= 1;
+            self.hearts[self.hp as usize].play(None)?;
+        }
+        while self.hp < health.hp {
+            let heart = &mut self.hearts[self.hp as usize];
+            heart.rewind();
+            heart.animations[heart.active].ongoing = false;
+            self.hp += 1;
+        }
+        Ok(())
+    }
+    pub fn next(&mut self, dt: Duration) {
+        for heart in &mut self.hearts {
+            heart.next(dt);
+        }
+    }
+    pub fn present(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        for (i, heart) in self.hearts.iter().enumerate() {
+            if (i as u32) < self.hp || heart.current().ongoing {
+                heart.present(canvas, 0, 0)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//how many of an item the character has, in the bottom right corner
+pub struct Counter<'a> {
+    label: String,
+    text: Text<'a>, //only rendered again when the count changes
+}
+impl<'a> Counter<'a> {
+    pub fn new(fonts: &FontCache<'a>, label: &str) -> Result<Self, String> {
+        let path = "assets/fonts/Aiden-v7DO.otf";
+        let mut text = Text::new(fonts, path, 60, Color::RGB(255, 255, 255), Point::new(0, 0))?;
+        text.align(Align::Right)?;
+        text.outline(fonts, path, 60, 3, Color::RGB(0, 0, 0))?;
+        text.shadow(Some((4, Color::RGBA(0, 0, 0, 120))))?;
+        Ok(Counter { label: label.to_string(), text })
+    }
+    pub fn sync(&mut self, count: u32) -> Result<(), String> {
+        let number = count.to_string();
+        self.text.set(&format!("{} {}", self.label, number))?;
+        //the number stands out, the whole counter is greyed out while there are none
+        let start = self.label.chars().count() + 1;
+        self.text.set_spans(vec![(start..start + number.len(), Color::RGB(255, 220, 120))])?;
+        self.text.set_color(if count > 0 {Color::RGB(255, 255, 255)} else {Color::RGB(140, 140, 140)})
+    }
+    pub fn present(&mut self, canvas: &mut WindowCanvas, display: &Display) -> Result<(), String> {
+        self.text.dst.set_right(display.width as i32 - 40);
+        self.text.dst.set_bottom(display.height as i32 - 30);
+        self.text.present(canvas)
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::{BTreeMap, HashMap}, str::FromStr, time::Duration};
+use sdl2::{
+    controller::{Axis, Button, GameController},
+    keyboard::{KeyboardState, Scancode},
+    mouse::{MouseButton, MouseState},
+};
+
+//what the player wants to do, independent of the key or button doing it
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Sprint,
+    Dash,
+    AttackLeft,
+    AttackRight,
+    AttackUp,
+    AttackDown,
+    Interact,
+    Pause,
+}
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::MoveLeft, Action::MoveRight, Action::MoveUp, Action::MoveDown,
+        Action::Sprint, Action::Dash,
+        Action::AttackLeft, Action::AttackRight, Action::AttackUp, Action::AttackDown,
+        Action::Interact, Action::Pause,
+    ];
+    //the action's name in the controls file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::Sprint => "sprint",
+            Action::Dash => "dash",
+            Action::AttackLeft => "attack_left",
+            Action::AttackRight => "attack_right",
+            Action::AttackUp => "attack_up",
+            Action::AttackDown => "attack_down",
+            Action::Interact => "interact",
+            Action::Pause => "pause",
+        }
+    }
+}
+impl FromStr for Action {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::ALL.into_iter().find(|action| action.name() == s).ok_or(format!("unknown action '{}'", s))
+    }
+}
+
+//a key, a controller button or one direction of a controller axis
+#[derive(PartialEq, Copy, Clone, Debug)]
+enum Source {
+    Key(Scancode),
+    Button(Button),
+    Axis(Axis, bool), //true for the positive direction
+}
+impl FromStr for Source {
+    type Err = String;
+    //SDL's names: "Left Shift" for keys, "pad:a" for buttons, "pad:leftx-" for half of an axis
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(name) = s.strip_prefix("pad:") else {
+            return Scancode::from_name(s).map(Source::Key).ok_or(format!("unknown key '{}'", s))
+        };
+        if let Some(axis) = name.strip_suffix('+') {
+            return Axis::from_string(axis).map(|axis| Source::Axis(axis, true)).ok_or(format!("unknown axis '{}'", s))
+        }
+        if let Some(axis) = name.strip_suffix('-') {
+            return Axis::from_string(axis).map(|axis| Source::Axis(axis, false)).ok_or(format!("unknown axis '{}'", s))
+        }
+        Button::from_string(name).map(Source::Button).ok_or(format!("unknown controller button '{}'", s))
+    }
+}
+
+//the bindings of the actions the settings file leaves out
+const DEFAULT_BINDINGS: &str = r#"
+move_left = ["A", "pad:leftx-", "pad:dpleft"]
+move_right = ["D", "pad:leftx+", "pad:dpright"]
+move_up = ["W", "pad:lefty-", "pad:dpup"]
+move_down = ["S", "pad:lefty+", "pad:dpdown"]
+sprint = ["Left Shift", "pad:leftshoulder"]
+dash = ["Left Alt", "pad:a"]
+attack_left = ["Left", "pad:rightx-"]
+attack_right = ["Right", "pad:rightx+"]
+attack_up = ["Up", "pad:righty-"]
+attack_down = ["Down", "pad:righty+"]
+interact = ["E", "pad:x"]
+pause = ["Escape", "pad:start"]
+"#;
+
+//sticks are ignored until they are tilted this far (0.0 - 1.0)
+const STICK_DEADZONE: f32 = 0.25;
+
+//one key, mouse button or action across ticks
+#[derive(Default, Copy, Clone)]
+struct Edge {
+    value: f32, //how much it is pressed (0.0 - 1.0), sticks can press halfway
+    previous: f32, //the same in the previous tick
+    held_for: Duration, //how long it has been held, zero in the tick it was pressed
+}
+impl Edge {
+    fn set(&mut self, value: f32, dt: Duration) {
+        self.previous = self.value;
+        self.value = value;
+        self.held_for = if self.held() && self.previous >= 0.5 {self.held_for + dt} else {Duration::ZERO};
+    }
+    fn held(&self) -> bool {
+        self.value >= 0.5
+    }
+    fn pressed(&self) -> bool {
+        self.held() && self.previous < 0.5
+    }
+    fn released(&self) -> bool {
+        !self.held() && self.previous >= 0.5
+    }
+    //true when pressed, then after being held for `delay` once every `interval`
+    fn repeated(&self, dt: Duration, delay: Duration, interval: Duration) -> bool {
+        if self.pressed() {
+            return true
+        }
+        if !self.held() || interval.is_zero() {
+            return false
+        }
+        //how many repeats there were after being held for `time`
+        let repeats = |time: Duration| if time < delay {0} else {(time - delay).as_nanos() / interval.as_nanos() + 1};
+        repeats(self.held_for) > repeats(self.held_for.saturating_sub(dt))
+    }
+}
+
+//the state of every action, read from the keyboard and the controller once per tick
+pub struct Controls {
+    names: BTreeMap<String, Vec<String>>, //the bindings as they are in the settings file
+    bindings: HashMap<Action, Vec<Source>>,
+    controller: Option<GameController>,
+    actions: HashMap<Action, Edge>,
+    keys: HashMap<Scancode, Edge>, //every key pressed so far, for things that don't have an action
+    buttons: HashMap<MouseButton, Edge>, //every mouse button pressed so far
+    dt: Duration, //the length of the last tick
+}
+impl Controls {
+    //the default bindings with the ones in `names` replacing them, names are e.g. "move_left" -> ["A", "pad:dpleft"]
+    //an action or a name that doesn't exist is reported and that action keeps its default
+    pub fn new(names: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        let mut controls = Controls {
+            names: BTreeMap::new(),
+            bindings: HashMap::new(),
+            controller: None,
+            actions: HashMap::new(),
+            keys: HashMap::new(),
+            buttons: HashMap::new(),
+            dt: Duration::ZERO,
+        };
+        controls.reset()?;
+        for (action, sources) in names {
+            if let Err(e) = action.parse().and_then(|action| controls.set(action, sources.clone())) {
+                eprintln!("{}, the default bindings are used for '{}'", e, action);
+            }
+        }
+        Ok(controls)
+    }
+    pub fn default_names() -> BTreeMap<String, Vec<String>> {
+        toml::from_str(DEFAULT_BINDINGS).expect("the default bindings are valid")
+    }
+    //going back to the default bindings
+    pub fn reset(&mut self) -> Result<(), String> {
+        for (action, sources) in Controls::default_names() {
+            self.set(action.parse()?, sources)?;
+        }
+        Ok(())
+    }
+    //the bindings by the actions' names, as they are in the settings
+    pub fn names(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.names
+    }
+    //the names bound to an action
+    pub fn bound(&self, action: Action) -> &[String] {
+        self.names.get(action.name()).map_or(&[], Vec::as_slice)
+    }
+    //replacing the bindings of an action, e.g. set(Action::Dash, vec!["Space".to_string()])
+    pub fn set(&mut self, action: Action, names: Vec<String>) -> Result<(), String> {
+        let sources = names.iter().map(|name| name.parse()).collect::<Result<Vec<Source>, String>>()?;
+        self.bindings.insert(action, sources);
+        self.names.insert(action.name().to_string(), names);
+        Ok(())
+    }
+    pub fn connect(&mut self, controller: GameController) {
+        self.controller = Some(controller);
+    }
+    //forgetting the controller if it's the one unplugged
+    pub fn disconnect(&mut self, id: i32) {
+        if self.controller.as_ref().map(|controller| controller.instance_id()) == Some(id) {
+            self.controller = None;
+        }
+    }
+    fn read(&self, source: Source, keyboard: &KeyboardState) -> f32 {
+        match (source, &self.controller) {
+            (Source::Key(key), _) => if keyboard.is_scancode_pressed(key) {1.0} else {0.0},
+            (Source::Button(button), Some(controller)) => if controller.button(button) {1.0} else {0.0},
+            (Source::Axis(axis, positive), Some(controller)) => {
+                let value = controller.axis(axis) as f32 / i16::MAX as f32;
+                let value = if positive {value} else {-value};
+                //0.0 at the edge of the deadzone, 1.0 at full tilt
+                ((value - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).clamp(0.0, 1.0)
+            }
+            _ => 0.0
+        }
+    }
+    //reading every binding, called once per tick so pressed and released last exactly one tick
+    pub fn update(&mut self, keyboard: &KeyboardState, mouse: &MouseState, dt: Duration) {
+        self.dt = dt;
+        for (action, sources) in &self.bindings {
+            let value = sources.iter().map(|source| self.read(*source, keyboard)).fold(0.0, f32::max);
+            self.actions.entry(*action).or_default().set(value, dt);
+        }
+        for key in keyboard.pressed_scancodes() {
+            self.keys.entry(key).or_default();
+        }
+        for (key, edge) in &mut self.keys {
+            edge.set(if keyboard.is_scancode_pressed(*key) {1.0} else {0.0}, dt);
+        }
+        for button in mouse.pressed_mouse_buttons() {
+            self.buttons.entry(button).or_default();
+        }
+        for (button, edge) in &mut self.buttons {
+            edge.set(if mouse.is_mouse_button_pressed(*button) {1.0} else {0.0}, dt);
+        }
+    }
+    fn action(&self, action: Action) -> Edge {
+        self.actions.get(&action).copied().unwrap_or_default()
+    }
+    pub fn value(&self, action: Action) -> f32 {
+        self.action(action).value
+    }
+    pub fn held(&self, action: Action) -> bool {
+        self.action(action).held()
+    }
+    //true only in the tick the action started
+    pub fn pressed(&self, action: Action) -> bool {
+        self.action(action).pressed()
+    }
+    //true only in the tick the action stopped
+    pub fn released(&self, action: Action) -> bool {
+        self.action(action).released()
+    }
+    //hold-to-repeat, see Edge::repeated
+    pub fn repeated(&self, action: Action, delay: Duration, interval: Duration) -> bool {
+        self.action(action).repeated(self.dt, delay, interval)
+    }
+    pub fn key_pressed(&self, key: Scancode) -> bool {
+        self.keys.get(&key).is_some_and(Edge::pressed)
+    }
+    pub fn mouse_pressed(&self, button: MouseButton) -> bool {
+        self.buttons.get(&button).is_some_and(Edge::pressed)
+    }
+    //true in the tick any key, mouse button or action started, for screens that go on with anything
+    pub fn any_pressed(&self) -> bool {
+        self.keys.values().chain(self.buttons.values()).chain(self.actions.values()).any(Edge::pressed)
+    }
+    pub fn mouse_repeated(&self, button: MouseButton, delay: Duration, interval: Duration) -> bool {
+        self.buttons.get(&button).is_some_and(|edge| edge.repeated(self.dt, delay, interval))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn edge_is_pressed_for_one_tick() {
+        let mut edge = Edge::default();
+        edge.set(1.0, TICK);
+        assert!(edge.pressed() && edge.held() && !edge.released());
+        edge.set(1.0, TICK);
+        assert!(!edge.pressed() && edge.held());
+        assert_eq!(edge.held_for, TICK);
+        edge.set(0.0, TICK);
+        assert!(edge.released() && !edge.held());
+        assert_eq!(edge.held_for, Duration::ZERO);
+        edge.set(0.0, TICK);
+        assert!(!edge.released());
+    }
+
+    #[test]
+    fn half_tilted_sticks_count_as_held() {
+        let mut edge = Edge::default();
+        edge.set(0.4, TICK);
+        assert!(!edge.held());
+        edge.set(0.6, TICK);
+        assert!(edge.pressed());
+    }
+
+    #[test]
+    fn edge_repeats_after_the_delay() {
+        let (delay, interval) = (Duration::from_millis(300), Duration::from_millis(100));
+        let mut edge = Edge::default();
+        let mut repeated = vec![];
+        for _ in 0..10 {
+            edge.set(1.0, TICK);
+            repeated.push(edge.repeated(TICK, delay, interval));
+        }
+        //pressed at 0ms, then repeated at 300ms and 400ms
+        assert_eq!(repeated, vec![true, false, false, false, false, false, true, false, true, false]);
+    }
+
+    #[test]
+    fn edge_doesnt_repeat_without_an_interval() {
+        let mut edge = Edge::default();
+        edge.set(1.0, TICK);
+        edge.set(1.0, Duration::from_secs(5));
+        assert!(!edge.repeated(Duration::from_secs(5), Duration::ZERO, Duration::ZERO));
+    }
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+//what the character carries, by item name
+#[derive(Default)]
+pub struct Inventory {
+    items: BTreeMap<String, u32>,
+}
+impl Inventory {
+    pub fn add(&mut self, item: &str, count: u32) {
+        *self.items.entry(item.to_string()).or_default() += count;
+    }
+    pub fn count(&self, item: &str) -> u32 {
+        self.items.get(item).copied().unwrap_or(0)
+    }
+    //using up `count` of an item, false (and nothing taken) if there isn't enough
+    pub fn take(&mut self, item: &str, count: u32) -> bool {
+        match self.items.get_mut(item) {
+            Some(have) if *have >= count => {
+                *have -= count;
+                if *have == 0 {
+                    self.items.remove(item);
+                }
+                true
+            }
+            _ => false
+        }
+    }
+}
@@ -1,32 +1,62 @@
 use kira::sound::static_sound::{PlaybackState, StaticSoundHandle};
 use sdl2::{
-    event::Event, 
-    keyboard::{Keycode, Scancode, KeyboardState}, 
+    event::{Event, WindowEvent}, 
+    keyboard::Keycode, 
     rect::{Rect, Point}, 
     render::{Canvas, TextureCreator}, 
-    image::{self, LoadTexture, InitFlag, LoadSurface},
+    image::{self, InitFlag, LoadSurface},
     render::{WindowCanvas, Texture},
-    pixels::Color,
     surface::{Surface, SurfaceRef}, 
     mixer::open_audio,
-    mouse::{MouseButton, MouseState},
 };
-use std::{time::{Instant, Duration}, thread::sleep, vec, f64::RADIX, os::windows};
-use sdl2::video::WindowContext;
-use sdl2::ttf;
+use std::{time::Duration, vec, f64::RADIX, f32::consts::FRAC_PI_4, collections::HashMap, str::FromStr, rc::Rc};
 use sdl2::mixer::{self, Channel, Chunk};
 use rand::prelude::*;
 
+mod assets;
+mod camera;
+mod dialogue;
+mod display;
+mod enemy;
+mod health;
+mod hud;
+mod input;
+mod inventory;
+mod manifest;
+mod nav;
+mod scenes;
+mod settings;
+mod spatial;
+mod text;
+mod tilemap;
+mod timing;
+mod ui;
+use assets::{FontCache, TextureCache};
+use camera::Camera;
+use dialogue::{Conversation, Flags, Script};
+use display::Display;
+use health::Health;
+use input::{Action, Controls};
+use inventory::Inventory;
+use manifest::Manifest;
+use nav::NavGrid;
+use scenes::{splash::Splash, Context, Input, SceneStack, Transition};
+use settings::Settings;
+use spatial::SpatialGrid;
+use tilemap::TileMap;
+use timing::{Clock, step};
+
 use kira::{
     manager::{
         AudioManager, AudioManagerSettings,
         backend::cpal::CpalBackend,
     },
-    sound::static_sound::{StaticSoundData, StaticSoundSettings},
     track::TrackBuilder,
     tween::Tween,
 };
 
+//the resolution the game is made for, entities are placed relative to its middle
+//the screen itself can be wider or narrower, see Display
 const SCREEN_WIDTH: u32 = 1920;
 const SCREEN_HEIGHT: u32 = 1080;
 fn main() -> Result<(), String> {
@@ -34,11 +64,18 @@ fn main() -> Result<(), String> {
     let sdl_context = sdl2::init()?;
     sdl_context.mouse().show_cursor(false);
     let video_subsystem = sdl_context.video()?;
+    let controllers = sdl_context.game_controller()?;
 
     let _image_context = image::init(InitFlag::PNG | InitFlag::JPG)?;
 
-    let mut window = video_subsystem.window("Dr. Cat Games", SCREEN_WIDTH, SCREEN_HEIGHT)
+    //the settings file is in the user's config directory, or next to the game if there is none
+    let config = sdl2::filesystem::pref_path("Dr. Cat Games", "jatek_demo").unwrap_or_default();
+    let settings = Settings::load(&format!("{}settings.toml", config));
+
+    let (width, height) = settings.resolution;
+    let mut window = video_subsystem.window("Dr. Cat Games", width, height)
         .position_centered()
+        .resizable()
         .build()
         .expect("could not init window (video subsys)");
 
@@ -47,377 +84,117 @@ fn main() -> Result<(), String> {
     // Set the icon of the window
     window.set_icon(surface);
 
-    let mut canvas = window.into_canvas().build()
+    let mut canvas = window.into_canvas();
+    if settings.vsync {
+        canvas = canvas.present_vsync();
+    }
+    let mut canvas = canvas.build()
         .expect("could not make canvas");
 
     //creating loader
     let loader: TextureCreator<_> = canvas.texture_creator();
-    
-    //defining character
-
-    let mut standing = Animation::new("assets/front.png", 1, 2, vec![(0, 0), (1, 0)], &loader);
-    standing.dur = Duration::from_millis(400);
-
-    let right = Animation::new("assets/movements/right.png", 1, 4, vec![(0, 0), (1, 0), (2, 0), (3, 0)], &loader);
-    let left = Animation::new("assets/movements/left.png", 1, 4, vec![(0, 0), (1, 0), (2, 0), (3, 0)], &loader);
-    let down = Animation::new("assets/movements/front.png", 1, 5, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)], &loader);
-    let up = Animation::new("assets/movements/back.png", 1, 6, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0)], &loader);
-
-    let frames = vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 0), (10, 0), (11, 0), (12, 0), (13, 0), (14, 0)];
-    let mut death = Animation::new("assets/death.png", 1, 15, frames, &loader);
-    death.dur = Duration::from_millis(100);
-    death.interruptable = false;
-    death.looped = false;
-    death.movable = false;
-
-    //dash character animation
-    let mut dash_left = Animation::new("assets/movements/dash_left.png", 1, 8, vec![(7, 0), (6, 0), (5, 0), (4, 0), (3, 0), (2, 0), (1, 0), (0, 0)], &loader);
-    dash_left.dur = Duration::from_millis(20);
-    dash_left.interruptable = false;
-    dash_left.looped = false;
-    let mut dash_right = Animation::new("assets/movements/dash_right.png", 1, 8, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0)], &loader);
-    dash_right.dur = Duration::from_millis(20);
-    dash_right.interruptable = false;
-    dash_right.looped = false;
-    let mut dash_front = Animation::new("assets/movements/dash_front.png", 1, 11, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 0), (10, 0)], &loader);
-    dash_front.dur = Duration::from_millis(20);
-    dash_front.interruptable = false;
-    dash_front.looped = false;
-    let mut dash_back = Animation::new("assets/movements/dash_back.png", 1, 8, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0)], &loader);
-    dash_back.dur = Duration::from_millis(20);
-    dash_back.interruptable = false;
-    dash_back.looped = false;
-    //let dash_back = dash_front.clone(&loader);
-    
-    //attack
-    let mut attack = Animation::new("assets/attack.png", 1, 8, vec![(0,0), (1,0), (2,0), (3, 0), (4,0), (5, 0), (6,0), (7,0)], &loader);
-    attack.interruptable = false;
-    attack.looped = false;
-    attack.movable = false;
-    let mut attack_left = Animation::new("assets/attack_left.png", 1, 8, vec![(0,0), (1,0), (2,0), (3, 0), (4,0), (5, 0), (6,0), (7,0)], &loader);
-    attack_left.interruptable = false;
-    attack_left.looped = false;
-    attack_left.movable = false;
-
-    let mut sponge = Entity::from(standing, 0, 0);
-    sponge.animations.push(left);
-    sponge.animations.push(right);
-    sponge.animations.push(up);
-    sponge.animations.push(down);
-    //5
-    sponge.animations.push(death);
-    //6-9
-    sponge.animations.push(dash_left);
-    sponge.animations.push(dash_right);
-    sponge.animations.push(dash_back);
-    sponge.animations.push(dash_front);
-
-    sponge.animations.push(attack);
-    sponge.animations.push(attack_left);
-    sponge.w = 100;
-    sponge.h = 100;
-    sponge.dst();
-    sponge.gen_hitbox(Rect::new(sponge.w as i32 / 2 - 15, sponge.h as i32 - 10, 25, 1));
-    
-    let mut sponge = Character {
-        x: 0, 
-        y: 0, 
-        rep: sponge, 
-        dir: Direction::Right, 
-        speed: 1,
-        dodge_cooldown: Instant::now(),
-    };
-    
-    
-    //defining hearts
-    let mut heart_loss = Animation::new("assets/selet.png", 1, 5, vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)], &loader);
-    heart_loss.dur = Duration::from_millis(400);
-    heart_loss.looped = false;
-    heart_loss.ongoing = false;
-    let mut heart = Entity::from(heart_loss.clone(&loader), -((SCREEN_WIDTH / 2) as i32) + 25, -((SCREEN_HEIGHT / 2) as i32) + 25);
-    heart.w = 50;
-    heart.h = 50;
-    heart.dst();
-
-    let mut heart2 = heart.clone(&loader);
-    heart2.x += 50;
-    heart2.dst();
-    
-    let mut heart3 = heart.clone(&loader);
-    heart3.x += 100;
-    heart3.dst();
-
-    //dash cooldown
-    let mut dash_anim = Animation::new("assets/dash.png", 7, 1, vec![(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6)], &loader);
-    dash_anim.looped = false;
-    dash_anim.ongoing = false;
-    dash_anim.dur = Duration::from_millis(286);
-    let mut dash = Entity::from(dash_anim, (SCREEN_WIDTH as i32 / 2)-44, -(SCREEN_HEIGHT as i32 / 2)+28);
-    dash.w *= 3;
-    dash.h *= 3;
-    dash.dst();
-
-    //logo
-    let logo = Animation::new("assets/UI/DRcatgameslogo.png", 1, 1, vec![(0, 0)], &loader);
-    let mut logo = Entity::from(logo, 0, 0);
-    logo.mult_w(0.5);
-    logo.mult_h(0.5);
-    logo.center();
-
-    //tree
-    let tree = Animation::new("assets/tree.png", 1, 1, vec![(0, 0)], &loader);
-    let mut tree = Entity::from(tree, 0, 0);
-    tree.w *= 2;
-    tree.h *= 2;
-    tree.dst();
-    tree.z_index = 3;
-    let mut tree_2 = tree.clone(&loader);
-    tree.gen_hitbox(Rect::new(tree.w as i32 / 2 - 15, tree.h as i32 -10, 25, 10));
-    tree.offset_x(100);
-    
-    
-    tree_2.x += 200;
-    tree_2.dst();
-    tree_2.gen_hitbox(Rect::new(tree_2.w as i32 / 2 - 15, tree_2.h as i32 -10, 25, 10));
-
-
-    let mut cat = Animation::new("assets/TX Player.png", 1, 1, vec![(0, 0)], &loader);
-    cat.current_frame.2 = Some(Rect::new(5, 13, 23, 45));
-    let mut cat = Entity::from(cat, -5670, -350);
-    cat.mult_w(0.7);
-    cat.z_index = 2;
-    cat.gen_hitbox(Rect::new(0, cat.h as i32 - 30, cat.w, 30));
-    let cat = Animal::from(3, cat);
-
-    //map test
-    let mut map = Animation::new("assets/map_base.png", 1, 1, vec![(0, 0)], &loader);
-    let mut map = Entity::from(map, 0, 0);
-    map.mult_h(7.0);
-    map.mult_w(7.0);
-    //map.z_index = 0;
-    map.gen_hitbox(Rect::new(0, 0, 0, 0));
-    let mut map = ComplexHitbox::new(map);
-    map.add_hitbox(Rect::new(416, 2922, 1284, 1213));
-    map.add_hitbox(Rect::new(1130, 3350, 77, 33));
-
-
-    let mut world = World::from(map, vec![tree, tree_2], 3, &loader);
-    world.x = 5670;
-    world.y = 370;
-    world.add(cat);
-
-    //Interactables
-
-    let mut test = Interactable::new(
-        Rect::new(1128, 3383, 86, 36),
-        Some({
-            fn test() -> bool {
-                println!("working?");
-                true
-            } test
-        }),
-        world.map.base.dst,
-    );
-    world.add_interaction(test);
 
     //text
-
     let ttf = sdl2::ttf::init().map_err(|e| e.to_string())?;
 
-
-    let mut text = Text::from(
-        "JÁTÉK KEZDÉSE", 
-        "assets/fonts/Aiden-v7DO.otf", 
-        100, 
-        Color::RGB(255, 255, 255),
-        Point::new(10, 10),
-        &ttf, &loader);
-    text.center();
-    text.dst.y -= 100;
-
     //sound
-    
     // Create an audio manager. This plays sounds and manages resources.
-    let mut manager = AudioManager::<CpalBackend>::new(AudioManagerSettings::default()).unwrap();
-    let track = manager.add_sub_track(TrackBuilder::default()).unwrap();
-    let track2 = manager.add_sub_track(TrackBuilder::default()).unwrap();
-    let track3 = manager.add_sub_track(TrackBuilder::default()).unwrap();
-    
-    let sound_data = StaticSoundData::from_file("assets/sounds/running_in_grass.mp3", StaticSoundSettings::new().track(&track)).unwrap();
-    let mut sound = manager.play(sound_data.clone()).unwrap();
-    //sound.set_volume(1f64, Tween { ..Default::default() });
-    let mut running = Audio {data: sound_data, current: sound};
-    
-    let sound_data = StaticSoundData::from_file("assets/sounds/slash.mp3", StaticSoundSettings::new().track(&track2)).unwrap();
-    let sound = manager.play(sound_data.clone()).unwrap();
-    let mut slash = Audio {data: sound_data, current: sound};
-    
-    //let sound_data = StaticSoundData::from_file("assets/sounds/background.mp3", StaticSoundSettings::default()).unwrap();
-    //let mut sound = manager.play(sound_data.clone()).unwrap();
-   // sound.set_volume(0.2f64, Tween { ..Default::default() });
-    //let mut background = Audio {data: sound_data, current: sound};
-
-  //  background.play(&mut manager);
-
-    // After a couple seconds...
-    // Cloning the sound data will not use any extra memory.
-
-    //UI
-
-    let mut test_button = Button::new(
-        Rect::new(0, 0, 400, 100), 
-        Some({
-                fn simple_callback() -> bool {
-                    false
-        } simple_callback
-        }), 
-        text.current);
-    test_button.center();
-
-
-    
+    let mut manager = AudioManager::<CpalBackend>::new(AudioManagerSettings::default()).map_err(|e| e.to_string())?;
+    let music = manager.add_sub_track(TrackBuilder::default()).map_err(|e| e.to_string())?;
+    let sfx = manager.add_sub_track(TrackBuilder::default()).map_err(|e| e.to_string())?;
+
+    //bindings the settings file gets wrong are left at the defaults
+    let controls = Controls::new(&settings.controls)?;
+
+    //everything the scenes share
+    let mut ctx = Context {
+        textures: TextureCache::new(&loader),
+        //loading the animation manifest
+        manifest: Manifest::load("assets/animations.toml")?,
+        fonts: FontCache::new(&ttf, &loader),
+        audio: manager,
+        music,
+        sfx,
+        alpha: 1.0,
+        display: Display::new(),
+        controls,
+        settings,
+    };
+    ctx.display.apply(&mut canvas, ctx.settings.window_mode, ctx.settings.resolution)?;
+    ctx.apply_volumes()?;
+    let mut scenes = SceneStack::new(Box::new(Splash::new(&mut ctx)?), &mut ctx)?;
 
     //game loop
     let mut event_pump = sdl_context.event_pump()?;
-    let mut i: u8 = 0;
-    let mut menu = true;
-    'running: for i in 0..255 {
+    let mut clock = Clock::new(60);
+    'running: while !scenes.is_empty() {
         //event handling
         for event in event_pump.poll_iter() {
             match event {
-                Event::Quit {..} |
-                Event::KeyDown {keycode: Some(Keycode::Escape), .. } => {
+                Event::Quit {..} => {
                     break 'running
                 },
-                _ => {}
+                Event::KeyDown {keycode: Some(Keycode::F11), repeat: false, .. } => {
+                    ctx.settings.window_mode = ctx.display.mode.next();
+                    ctx.display.outdated = true;
+                }
+                Event::Window {win_event: WindowEvent::SizeChanged(..), .. } => {
+                    ctx.display.fit(&mut canvas)?;
+                    scenes.resize(&mut ctx);
+                }
+                Event::ControllerDeviceAdded {which, .. } => {
+                    if let Ok(controller) = controllers.open(which) {
+                        ctx.controls.connect(controller);
+                    }
+                }
+                Event::ControllerDeviceRemoved {which, .. } => ctx.controls.disconnect(which),
+                event => scenes.event(&mut ctx, &event)?
             }
         }
-        canvas.set_draw_color(Color::RGB(255-i, 255-i, 255-i));
-        canvas.clear();
-
-        logo.present(&mut canvas,0,0)?;
 
-        canvas.present();
-        //ticks
-        sleep(Duration::new(0, 1_000_000_000u32 / 60));
-    }
-
-    'running: loop {
-        
-        canvas.clear();
-
-        //get mouse
         let mouse = event_pump.mouse_state();
-        //get keyboard
-        let keyboard = event_pump.keyboard_state();
-
-
-        if menu {
-            if let Some(res) = test_button.exec(&mouse) {
-                let res = res();
-                println!("{:?}", res);
-                menu = res;
-            }
-
-            test_button.present(&mut canvas)?;
-
-
+        let input = Input {
+            //get keyboard
+            keyboard: event_pump.keyboard_state(),
+            //the mouse in logical coordinates, the window may be scaled
+            cursor: ctx.display.to_logical(mouse.x(), mouse.y()),
+            //get mouse
+            mouse,
+        };
+
+        //updates, each one simulating dt
+        clock.advance();
+        while clock.tick() && !scenes.is_empty() {
+            ctx.controls.update(&input.keyboard, &input.mouse, clock.dt());
+            scenes.update(&mut ctx, &input, clock.dt())?;
         }
-
-        
-        else if !menu {
-
-            if keyboard.is_scancode_pressed(Scancode::K) {
-                heart.play(None);
-                heart2.play(None);
-                heart3.play(None);
-                sponge.rep.play(Some(5));
-            }
-            if keyboard.is_scancode_pressed(Scancode::Right) {
-                slash.play(&mut manager);
-                sponge.rep.play(Some(10));
-            }
-            else if keyboard.is_scancode_pressed(Scancode::Left) {
-                slash.play(&mut manager);
-                sponge.rep.play(Some(11));
-            }
-
-            let mut moved = false;
-            if sponge.rep.animations[sponge.rep.active].movable {
-                //checking for sprint
-                sponge.speed(&keyboard, &mut dash);
-
-                //movement
-                moved = sponge.movement(&keyboard, &mut world);
-            }
-            //if not moved then stand
-            if !moved {
-                sponge.rep.switch_to(0);
-                running.stop(0);
-            } else {
-                running.play(&mut manager);
-            }
-
-            world.reorder_char(&mut sponge);
-            //play next frame of animations
-            sponge.rep.next();
-            heart.next();
-            heart2.next();
-            heart3.next();
-            dash.next();
-            world.do_behaviours(&sponge);
-
-            //map.dst();
-
-
-            //new_frame
-            i = (i + 1) % 255;
-            //rendering
-
-            canvas.set_draw_color(Color::RGB(i, 64, 255 - i));
-        
-
-            world.present(&mut canvas, &sponge)?;
-
-            if let Some((x, i)) = world.check_interact(&sponge, &keyboard, &mut canvas) {
-                println!("{:?} {}", x, i)
-            }
-
-            heart.present(&mut canvas, 0, 0)?;
-            heart2.present(&mut canvas, 0, 0)?;
-            heart3.present(&mut canvas, 0, 0)?;
-            dash.present(&mut canvas, 0, 0)?;
-
+        if ctx.display.outdated {
+            ctx.display.outdated = false;
+            ctx.display.apply(&mut canvas, ctx.settings.window_mode, ctx.settings.resolution)?;
+            scenes.resize(&mut ctx);
         }
 
-        //event handling
-        for event in event_pump.poll_iter() {
-            match event {
-                Event::Quit {..} |
-                Event::KeyDown {keycode: Some(Keycode::Escape), .. } => {
-                    break 'running
-                },
-                _ => {}
-            }
-        }
+        //rendering
+        ctx.alpha = clock.alpha();
+        scenes.render(&mut ctx, &mut canvas, &input)?;
 
         canvas.present();
-        //ticks
-        sleep(Duration::new(0, 1_000_000_000u32 / 60));
+        clock.idle(ctx.settings.frame_cap);
     }
 
+    ctx.save_settings();
     Ok(())
 }
-struct ComplexHitbox<'a> {
-    base: Entity<'a>,
+struct ComplexHitbox {
+    area: Rect, //the map the hitboxes are relative to
     ideal: Vec<Rect>,
     real: Vec<Rect>
 }
-impl<'a> ComplexHitbox<'a> {
+impl ComplexHitbox {
     fn create_real(&self, hitbox: Rect) -> Rect {
         Rect::new(
-            self.base.dst.x() + hitbox.x(),
-            self.base.dst.y() + hitbox.y(),
+            self.area.x() + hitbox.x(),
+            self.area.y() + hitbox.y(),
             hitbox.width(),
             hitbox.height()
         )
@@ -432,32 +209,36 @@ impl<'a> ComplexHitbox<'a> {
             self.add_hitbox(ideal)
         }
     }
-    fn collide_all(&self, other: Rect, world_x: i32, world_y: i32) -> bool {
-        for real in &self.real {
-            let mut copy = *real;
-            copy.set_x(copy.x() + world_x);
-            copy.set_y(copy.y() + world_y);
-            if let Some(x) = copy.intersection(other) {
-                if x != other {
-                    println!("{}", copy.has_intersection(other));
-                    return true
-                }
-            }
-        }
-        false
+    //whether `other` runs into one of the real hitboxes, being entirely inside one is allowed
+    fn blocks(real: Rect, other: Rect) -> bool {
+        real.intersection(other).is_some_and(|x| x != other)
     }
-    fn new(base: Entity<'a>) -> Self {
-        let base_hitbox = base.hitbox;
-        let new = ComplexHitbox { base, ideal: vec![], real: vec![base_hitbox] };
-        new
+    fn new(area: Rect) -> Self {
+        ComplexHitbox { area, ideal: vec![], real: vec![] }
+    }
+}
+//what an interaction can change
+struct Game<'g, 'a> {
+    world: &'g mut World<'a>,
+    char: &'g mut Character<'a>,
+    inventory: &'g mut Inventory,
+    flags: &'g mut Flags,
+    dialogue: &'g mut Option<Conversation>, //the conversation going on, the world waits while there is one
+}
+impl Game<'_, '_> {
+    //starting a conversation, the scripts are loaded with the level
+    fn talk(&mut self, script: &Rc<Script>) {
+        *self.dialogue = Conversation::start(script.clone(), self.flags, self.inventory);
     }
 }
-struct Interactable {
+//run when the character uses an interactable, the transition can e.g. push a scene on top of the game
+type Interaction<'a> = Rc<dyn Fn(&mut Game<'_, 'a>) -> Result<Transition<'a>, String> + 'a>;
+struct Interactable<'a> {
     hitbox: Rect,
-    callback: Option<fn() -> bool>,
+    callback: Option<Interaction<'a>>,
 }
-impl Interactable{
-    fn new(hitbox: Rect, callback: Option<fn() -> bool>, map_dst: Rect) -> Self {
+impl<'a> Interactable<'a> {
+    fn new(hitbox: Rect, callback: Option<Interaction<'a>>, map_dst: Rect) -> Self {
         let hitbox = Rect::new(
             map_dst.x() + hitbox.x(),
             map_dst.y() + hitbox.y(),
@@ -468,53 +249,17 @@ impl Interactable{
             hitbox, callback
         }
     }
-    fn check(&self, world_x: i32, world_y: i32, hitbox: Rect) -> bool {
-        let mut interactable_hitbox = self.hitbox;
-        interactable_hitbox.x += world_x;
-        interactable_hitbox.y += world_y;
-        if hitbox.has_intersection(interactable_hitbox) {
-            println!("yoooo");
-            return true
-        }
-        false
+    fn check(&self, hitbox: Rect) -> bool {
+        hitbox.has_intersection(self.hitbox)
     }
-    fn exec(&self, keyboard: &KeyboardState) -> Option<fn() -> bool> {
-        if keyboard.is_scancode_pressed(Scancode::E) {
-            return self.callback
+    fn exec(&self, controls: &Controls) -> Option<Interaction<'a>> {
+        if controls.pressed(Action::Interact) {
+            return self.callback.clone()
         }
         None
     }
     
 }
-struct Button<'a, T> {
-    hitbox: Rect,
-    callback: Option<fn() -> T>,
-    appearance: Texture<'a>
-}
-impl<'a, T> Button<'a, T> {
-    fn new(hitbox: Rect, callback: Option<fn() -> T>, appearance: Texture<'a>) -> Self {
-        Button {
-            hitbox, callback, appearance
-        }
-    }
-    fn check(&self, mouse: &MouseState) -> bool {
-        self.hitbox.contains_point(Point::new(mouse.x(), mouse.y()))
-    }
-    fn exec(&self, mouse: &MouseState) -> Option<fn() -> T> {
-        if self.check(mouse) && mouse.left() {
-            return self.callback
-        }
-        None
-    }
-    fn present(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
-        canvas.copy(&self.appearance, None, self.hitbox)?;
-        Ok(())
-    }
-    fn center(&mut self) {
-        self.hitbox.x = (SCREEN_WIDTH as i32 - self.hitbox.w as i32) / 2;
-        self.hitbox.y = (SCREEN_HEIGHT as i32 - self.hitbox.h as i32) / 2;
-    }
-}
 struct Audio {
     data: kira::sound::static_sound::StaticSoundData,
     current: kira::sound::static_sound::StaticSoundHandle
@@ -525,8 +270,19 @@ impl Audio {
             self.current = manager.play(self.data.clone()).unwrap();
         }
     }
-    fn stop(&mut self, time: u64) {
+    fn pause(&mut self) {
         if self.current.state() == PlaybackState::Playing {
+            self.current.pause(Tween::default()).unwrap();
+        }
+    }
+    fn resume(&mut self) {
+        if self.current.state() == PlaybackState::Paused {
+            self.current.resume(Tween::default()).unwrap();
+        }
+    }
+    //paused sounds are stopped too, they would stay in the manager otherwise
+    fn stop(&mut self, time: u64) {
+        if !matches!(self.current.state(), PlaybackState::Stopping | PlaybackState::Stopped) {
             self.current.stop(Tween {
                 duration: Duration::from_secs(time),
                 ..Default::default()
@@ -534,44 +290,6 @@ impl Audio {
         }    
     }
 }
-struct Text<'a> {
-    content: &'a str,
-    size: u16,
-    color: Color,
-    current: Texture<'a>,
-    dst: Rect
-}
-impl<'a> Text<'a> {
-    fn from(content: &'a str, path: &str, size: u16, color: Color, point: Point, ttf: &'a ttf::Sdl2TtfContext, loader: &'a TextureCreator<WindowContext>) -> Self {
-        let font = ttf.load_font(path, size).map_err(|e| e.to_string()).unwrap();
-
-        let surface = font
-            .render(content)
-            .blended(color)
-            .map_err(|e| e.to_string())
-            .unwrap();
-
-        let text = loader
-            .create_texture_from_surface(&surface)
-            .map_err(|e| e.to_string())
-            .unwrap();
-        let (width, height) = text.get_size();
-        let dst = Rect::new(point.x(), point.y(), width, height);
-        Text {content: &content, size, color, current: text, dst}
-    }
-    fn present(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
-        canvas.copy(&self.current, None, self.dst)?;
-        Ok(())
-    }
-    fn center(&mut self) {
-        self.dst.x = (SCREEN_WIDTH as i32 - self.dst.w as i32) / 2;
-        self.dst.y = (SCREEN_HEIGHT as i32 - self.dst.h as i32) / 2;
-    }
-    fn to_entity(some: Text) -> Entity {
-        let anim = Animation::from_texture(some.current, 1, 1, vec![(0, 0)]);
-        Entity::from(anim, some.dst.x(), some.dst.y())
-    }
-}
 struct Static<'a> {
     img: Texture<'a>,
     x: i32,
@@ -592,9 +310,10 @@ struct Static<'a> {
             dst
         }
     }
-    fn center(&mut self) {
-        self.x = (SCREEN_WIDTH as i32 - self.w as i32) / 2;
-        self.y = (SCREEN_HEIGHT as i32 - self.h as i32) / 2;
+    fn center(&mut self, display: &Display) {
+        let corner = display.center(self.w, self.h);
+        self.x = corner.x();
+        self.y = corner.y();
         self.dst.x = self.x;
         self.dst.y = self.y;
     }
@@ -602,12 +321,32 @@ struct Static<'a> {
         self.dst = Rect::new(self.x, self.y, self.w, self.h);
     }
 }
+#[derive(PartialEq, Copy, Clone, Debug)]
+enum Condition {
+    Healthy,
+    Hurt, //recovering from a hit
+    Dying, //out of health, but still playing its death animation
+    Dead, //removed from the world on the next update
+}
 trait Presentable {
     fn get_hitbox(&self) -> Rect;
-    fn get_z_index(&self) -> u8;
+    fn get_body(&self) -> Rect; //the area the entity is drawn to, used for hit detection
+    fn get_layer(&self) -> Layer;
     fn present(&self, canvas: &mut WindowCanvas, world_x: i32, world_y: i32) -> Result<(), String>;
-    fn check_move(&mut self, x: i32, y: i32, hitbox: Rect) -> bool;
-    fn behave(&mut self, char: &Character, x: i32, y: i32);
+    fn behave(&mut self, char: &mut Character, world: &World, dt: Duration);
+    //taking a hit, returns false if the entity wasn't hurt by it
+    fn hurt(&mut self, _damage: u32) -> bool {
+        false
+    }
+    //moving by a distance the world already checked, e.g. the knockback of a hit
+    fn push(&mut self, _dx: i32, _dy: i32) {}
+    fn condition(&self) -> Condition {
+        Condition::Healthy
+    }
+    //the path of the conversation started by talking to the entity
+    fn dialogue(&self) -> Option<&str> {
+        None
+    }
 }
 impl Presentable for Entity<'_> {
     fn present(&self, canvas: &mut WindowCanvas, world_x: i32, world_y: i32) -> Result<(), String> {
@@ -616,31 +355,45 @@ impl Presentable for Entity<'_> {
         destination.y += world_y;
 
         canvas.copy(
-            &self.animations[self.active].sheet, 
-            self.animations[self.active].get_src(),
+            &self.current().sheet, 
+            self.current().get_src(),
             destination)?;
         //canvas.fill_rect(self.hitbox)?;
         Ok(())
     }
-    fn check_move(&mut self, x: i32, y: i32, hitbox: Rect) -> bool {
-        let mut entity_hitbox = self.hitbox;
-        entity_hitbox.x += x;
-        entity_hitbox.y += y;
-        if hitbox.has_intersection(entity_hitbox) {
-            return false
-        }
-        true
-    }
     fn get_hitbox(&self) -> Rect {
         self.hitbox
     }
-    fn get_z_index(&self) -> u8 {
-        self.z_index
+    fn get_body(&self) -> Rect {
+        self.dst
     }
-    fn behave(&mut self, char: &Character, x: i32, y: i32) {
+    fn get_layer(&self) -> Layer {
+        self.layer
+    }
+    fn behave(&mut self, char: &mut Character, world: &World, dt: Duration) {
         return
     }
 }
+//`hitbox` stretched by `step` pixels towards `dir`, the area something passes through when it moves
+fn sweep(mut hitbox: Rect, dir: Direction, step: i32) -> Rect {
+    match dir {
+        Direction::Down => {
+            hitbox.h += step;
+        }
+        Direction::Up => {
+            hitbox.y -= step;
+            hitbox.h += step;
+        }
+        Direction::Right => {
+            hitbox.w += step;
+        }
+        Direction::Left => {
+            hitbox.x -= step;
+            hitbox.w += step;
+        }
+    }
+    hitbox
+}
 fn random(start: i32, end: i32) -> i32 {
     let mut rng = thread_rng();
     rng.gen_range(start..end)
@@ -649,17 +402,29 @@ fn chance(percent: u8) -> bool {
     random(1, 101) < percent as i32
 }
 struct Animal<'a> {
-    can_move: bool,
     dir: Direction,
-    speed: i32,
+    speed: i32, //pixels per second
+    step: i32, //pixels moved this tick
+    health: Health,
+    stun: Duration, //the animal doesn't move until this runs out after a hit
+    dialogue: Option<String>, //the conversation started by talking to the animal
     entity: Entity<'a>
 }
 impl<'a> Animal<'a> {
     fn from(speed: i32, entity: Entity<'a>) -> Self {
-        return Animal {can_move: false, dir: Direction::Down, speed, entity};
-    }
-    fn decide(&mut self) {
-        if chance(95) {return}
+        return Animal {
+            dir: Direction::Down,
+            speed,
+            step: 0,
+            health: Health::new(3, Duration::from_millis(400)),
+            stun: Duration::ZERO,
+            dialogue: None,
+            entity
+        };
+    }
+    //turning to a random direction, except for `keep` percent of the time
+    fn decide(&mut self, keep: u8) {
+        if chance(keep) {return}
         let new_dir: u8 = random(0, 4) as u8;
         self.dir = match new_dir {
             0 => Direction::Right,
@@ -670,273 +435,592 @@ impl<'a> Animal<'a> {
         }
     }
     fn move_dir(&mut self) {
-        if chance(50) {return}
-        match self.dir {
-            Direction::Down => {self.entity.offset_y(self.speed)},
-            Direction::Up => {self.entity.offset_y(-self.speed)},
-            Direction::Right => {self.entity.offset_x(self.speed)},
-            Direction::Left => {self.entity.offset_x(-self.speed)},
+        if self.entity.has(Anim::Walk(self.dir)) {
+            self.entity.switch_to(Anim::Walk(self.dir)).unwrap();
         }
-    }
-    fn can_move_self(&mut self, char: &Character, x: i32, y: i32) -> bool {
-        let mut hitbox = self.entity.hitbox;
-        hitbox.x += x;
-        hitbox.y += y;
-
         match self.dir {
-            Direction::Down => {
-                //hitbox.y += char.speed;
-                hitbox.h += self.speed + char.speed;
-            }
-            Direction::Up => {
-                hitbox.y -= self.speed + char.speed;
-                hitbox.h += self.speed + char.speed;
-            }
-            Direction::Right => {
-                hitbox.w += self.speed + char.speed;
-            }
-            Direction::Left => {
-                hitbox.x -= self.speed + char.speed;
-                hitbox.w += self.speed + char.speed;
-            }
+            Direction::Down => {self.entity.offset_y(self.step)},
+            Direction::Up => {self.entity.offset_y(-self.step)},
+            Direction::Right => {self.entity.offset_x(self.step)},
+            Direction::Left => {self.entity.offset_x(-self.step)},
         }
-        if hitbox.has_intersection(char.rep.hitbox) {
-            return false;
+    }
+    //counting down the invulnerability and the stun after a hit, true while still stunned
+    fn recover(&mut self, dt: Duration) -> bool {
+        self.health.next(dt);
+        if !self.stun.is_zero() {
+            self.stun = self.stun.saturating_sub(dt);
+            return true
         }
-        true
+        false
+    }
+    //false if the next step would walk into the player
+    fn can_move_self(&self, char: &Character) -> bool {
+        //the player might step towards the animal in the same tick
+        !sweep(self.entity.hitbox, self.dir, self.step + char.step).has_intersection(char.rep.hitbox)
+    }
+    //false if the next step would walk into the player, the map or another entity
+    fn can_move(&self, char: &Character, world: &World) -> bool {
+        self.can_move_self(char) && !world.collides(sweep(self.entity.hitbox, self.dir, self.step))
     }
 }
 impl Presentable for Animal<'_> {
     fn present(&self, canvas: &mut WindowCanvas, world_x: i32, world_y: i32) -> Result<(), String> {
+        if !self.health.visible() {
+            return Ok(())
+        }
         self.entity.present(canvas, world_x, world_y)
     }
-    fn check_move(&mut self, x: i32, y: i32, hitbox: Rect) -> bool {
-        let result = self.entity.check_move(x, y, hitbox);
-        self.can_move = result;
-        result
-    }
     fn get_hitbox(&self) -> Rect {
         self.entity.hitbox
     }
-    fn get_z_index(&self) -> u8 {
-        self.entity.z_index
+    fn get_body(&self) -> Rect {
+        self.entity.dst
+    }
+    fn get_layer(&self) -> Layer {
+        self.entity.layer
     }
-    fn behave(&mut self, char: &Character, x: i32, y: i32) {
-        self.decide();
-        if self.can_move_self(char, x, y) {
+    fn behave(&mut self, char: &mut Character, world: &World, dt: Duration) {
+        if self.recover(dt) {
+            return
+        }
+        self.step = step(self.speed, dt);
+        self.decide(95);
+        if !chance(50) && self.can_move(char, world) {
             self.move_dir();
         }
+        self.entity.next(dt);
+    }
+    fn hurt(&mut self, damage: u32) -> bool {
+        if self.health.damage(damage) == 0 {
+            return false
+        }
+        self.stun = Duration::from_millis(300);
+        true
+    }
+    fn push(&mut self, dx: i32, dy: i32) {
+        self.entity.offset_x(dx);
+        self.entity.offset_y(dy);
+    }
+    fn dialogue(&self) -> Option<&str> {
+        self.dialogue.as_deref()
+    }
+    fn condition(&self) -> Condition {
+        if self.health.is_dead() {
+            Condition::Dead
+        } else if !self.stun.is_zero() {
+            Condition::Hurt
+        } else {
+            Condition::Healthy
+        }
     }
 }
+//how far a hitbox got when moved through the world
+struct Motion {
+    dx: i32,
+    dy: i32,
+    normal: (i32, i32), //which way the walls it ran into face, 0 on an axis where nothing stopped it
+}
+//what can be in the way of a hitbox
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone, Debug)]
+enum Solid {
+    Wall(usize), //the index in the map's real hitboxes
+    Entity(usize, usize), //the layer and the index in the layer
+}
 struct World<'a> {
-    x: i32,
-    y: i32,
-    map: ComplexHitbox<'a>,
+    camera: Camera,
+    map: ComplexHitbox,
+    tiles: TileMap<'a>,
     entities: Vec<Vec<Box<dyn Presentable + 'a>>>,
-    interactables: Vec<Interactable>,
+    interactables: Vec<Interactable<'a>>,
+    nav: NavGrid, //where NPCs can walk, baked from the map and the entities given to World::from
+    solids: SpatialGrid<Solid>, //the map's and the entities' hitboxes
+    bodies: SpatialGrid<(usize, usize)>, //the entities' bodies by layer and index, for hitting and drawing
+    zones: SpatialGrid<usize>, //the interactables' hitboxes
+    behaving: Option<Solid>, //the entity being moved by do_behaviours or a hit, it can't run into itself
     e: Entity<'a>,
 }
 
 
 impl<'a> World<'a> {
-    fn from(map: ComplexHitbox<'a>, entities: Vec<Entity<'a>>, highest: u8, loader: &'a TextureCreator<WindowContext>) -> Self {
-        let mut entities_n: Vec<Vec<Box<dyn Presentable>>> = vec![vec![]];
-        for _ in 0..highest + 1 {
-            entities_n.push(vec![]);
+    fn from(map: ComplexHitbox, tiles: TileMap<'a>, entities: Vec<Entity<'a>>, textures: &TextureCache<'a>) -> Result<Self, String> {
+        let mut entities_n: Vec<Vec<Box<dyn Presentable>>> = Layer::ALL.iter().map(|_| vec![]).collect();
+        //the map and the starting entities don't move, so they are baked into the grid once
+        let mut nav = NavGrid::new(map.area, 32, 40);
+        let mut solids = SpatialGrid::new(256);
+        for (i, hitbox) in map.real.iter().enumerate() {
+            nav.wall(*hitbox);
+            solids.insert(Solid::Wall(i), *hitbox);
         }
         for entity in entities {
-            entities_n[entity.z_index as usize].push(Box::new(entity));
+            nav.block(entity.hitbox);
+            entities_n[entity.layer as usize].push(Box::new(entity));
         }
-        World {
-            x: 0,
-            y: 0,
+        let mut camera = Camera::new();
+        camera.bounds = Some(map.area);
+        camera.deadzone = (200, 120);
+        camera.smoothing = 8.0;
+        let mut world = World {
+            camera,
             map,
+            tiles,
             entities: entities_n,
             interactables: vec![],
+            nav,
+            solids,
+            bodies: SpatialGrid::new(256),
+            zones: SpatialGrid::new(256),
+            behaving: None,
             e: Entity::from(
-                Animation::new("assets/E.png", 1, 1, vec![(0, 0)], loader),
+                Animation::new("assets/E.png", 1, 1, vec![(0, 0)], textures)?,
                 0, 0
             )
-        }
+        };
+        world.reindex();
+        Ok(world)
     }
-    fn add_interaction(&mut self, interactable: Interactable) {
+    fn add_interaction(&mut self, interactable: Interactable<'a>) {
+        self.zones.insert(self.interactables.len(), interactable.hitbox);
         self.interactables.push(interactable);
     }
     fn add<T: Presentable + 'a>(&mut self, entity: T) {
-        self.entities[entity.get_z_index() as usize].push(Box::new(entity));
-    }
-    fn can_move(&mut self, char: &mut Character, dir: Direction) -> bool {
-        let mut can_move = true;
-        let mut hitbox = char.rep.hitbox;
-
-        match dir {
-            Direction::Down => {
-                //hitbox.y += char.speed;
-                hitbox.h += char.speed;
+        let z = entity.get_layer() as usize;
+        self.entities[z].push(Box::new(entity));
+        self.index(z, self.entities[z].len() - 1);
+    }
+    //putting an entity into the grids, or following it after it moved
+    fn index(&mut self, z: usize, i: usize) {
+        let entity = &self.entities[z][i];
+        let (hitbox, body) = (entity.get_hitbox(), entity.get_body());
+        self.track(z, i, hitbox, body);
+    }
+    fn track(&mut self, z: usize, i: usize, hitbox: Rect, body: Rect) {
+        self.solids.update(Solid::Entity(z, i), hitbox);
+        self.bodies.update((z, i), body);
+    }
+    //indexing every entity again, after some were removed and the rest got new indices
+    fn reindex(&mut self) {
+        self.solids.retain(|solid| matches!(solid, Solid::Wall(_)));
+        self.bodies.retain(|_| false);
+        for z in 0..self.entities.len() {
+            for i in 0..self.entities[z].len() {
+                self.index(z, i);
             }
-            Direction::Up => {
-                hitbox.y -= char.speed;
-                hitbox.h += char.speed;
+        }
+    }
+    //moving `hitbox` as far as it can go, one axis after the other so it slides along walls
+    fn slide(&self, hitbox: Rect, dx: i32, dy: i32) -> Motion {
+        let (dx, normal_x) = self.sweep_axis(hitbox, dx, true);
+        let moved = Rect::new(hitbox.x() + dx, hitbox.y(), hitbox.width(), hitbox.height());
+        let (dy, normal_y) = self.sweep_axis(moved, dy, false);
+        Motion { dx, dy, normal: (normal_x, normal_y) }
+    }
+    //the distance `hitbox` can move along one axis and the normal of the wall stopping it
+    //every edge on the way counts, so a fast dash can't skip over a thin hitbox
+    fn sweep_axis(&self, hitbox: Rect, distance: i32, horizontal: bool) -> (i32, i32) {
+        if distance == 0 {
+            return (0, 0)
+        }
+        //(start, end) along the axis of the motion, then across it
+        let span = |rect: Rect| if horizontal {
+            (rect.left(), rect.right(), rect.top(), rect.bottom())
+        } else {
+            (rect.top(), rect.bottom(), rect.left(), rect.right())
+        };
+        let (start, end, across_start, across_end) = span(hitbox);
+        let forward = distance > 0;
+        //the free space before an obstacle, map hitboxes can also be walls around the hitbox (see ComplexHitbox::blocks)
+        let gap = |obstacle: Rect, from_inside: bool| -> Option<i32> {
+            let (o_start, o_end, o_across_start, o_across_end) = span(obstacle);
+            if o_across_end <= across_start || across_end <= o_across_start {
+                return None
             }
-            Direction::Right => {
-                hitbox.w += char.speed;
+            if from_inside && obstacle.contains_rect(hitbox) {
+                Some(if forward {o_end - end} else {start - o_start})
+            } else if forward && o_start >= end {
+                Some(o_start - end)
+            } else if !forward && o_end <= start {
+                Some(start - o_end)
+            } else {
+                None
             }
-            Direction::Left => {
-                hitbox.x -= char.speed;
-                hitbox.w += char.speed;
+        };
+        let mut allowed = distance.abs();
+        let dir = match (horizontal, forward) {
+            (true, true) => Direction::Right,
+            (true, false) => Direction::Left,
+            (false, true) => Direction::Down,
+            (false, false) => Direction::Up,
+        };
+        for (solid, obstacle) in self.solids.query(sweep(hitbox, dir, allowed)) {
+            if self.behaving == Some(solid) {
+                continue
             }
+            let from_inside = matches!(solid, Solid::Wall(_));
+            allowed = allowed.min(gap(obstacle, from_inside).unwrap_or(allowed));
         }
-
-        if self.map.collide_all(hitbox, self.x, self.y) {
-            return false
-        }
+        let normal = if allowed < distance.abs() {-distance.signum()} else {0};
+        (allowed * distance.signum(), normal)
+    }
+    //whether `hitbox` runs into the map or an entity, used for the character and NPCs alike
+    fn collides(&self, hitbox: Rect) -> bool {
+        self.solids.query(hitbox).into_iter().any(|(solid, obstacle)| match solid {
+            _ if self.behaving == Some(solid) => false,
+            Solid::Wall(_) => ComplexHitbox::blocks(obstacle, hitbox),
+            Solid::Entity(..) => obstacle.has_intersection(hitbox),
+        })
+    }
+    fn do_behaviours(&mut self, char: &mut Character, dt: Duration) {
+        let count: usize = self.entities.iter().map(Vec::len).sum();
         for layer in &mut self.entities {
-            for entity in layer {
-                if !entity.check_move(self.x, self.y, hitbox) {
-                    return false
+            layer.retain(|entity| entity.condition() != Condition::Dead);
+        }
+        if self.entities.iter().map(Vec::len).sum::<usize>() != count {
+            self.reindex();
+        }
+        for z in 0..self.entities.len() {
+            //taking the layer out while its entities behave so they can check their moves against the rest of the world
+            let mut layer = std::mem::take(&mut self.entities[z]);
+            for (i, entity) in layer.iter_mut().enumerate() {
+                let (hitbox, body) = (entity.get_hitbox(), entity.get_body());
+                self.behaving = Some(Solid::Entity(z, i));
+                entity.behave(char, self, dt);
+                //only the ones that moved are put into the grids again
+                if entity.get_hitbox() != hitbox || entity.get_body() != body {
+                    self.track(z, i, entity.get_hitbox(), entity.get_body());
                 }
             }
+            self.behaving = None;
+            self.entities[z] = layer;
         }
-
-        true
     }
-    fn do_behaviours(&mut self, char: &Character ) {
-        for layer in &mut self.entities {
-            for entity in layer {
-                entity.behave(char, self.x, self.y);
+    //hurting everything whose body overlaps `area`, returns the number of entities hit
+    fn hit(&mut self, area: Rect, damage: u32, knockback: (i32, i32)) -> u32 {
+        let mut hits = 0;
+        for ((z, i), body) in self.bodies.query(area) {
+            if body.has_intersection(area) && self.entities[z][i].hurt(damage) {
+                hits += 1;
+                //knocked back only as far as the map and the other entities let it
+                self.behaving = Some(Solid::Entity(z, i));
+                let motion = self.slide(self.entities[z][i].get_hitbox(), knockback.0, knockback.1);
+                self.behaving = None;
+                self.entities[z][i].push(motion.dx, motion.dy);
+                self.index(z, i);
             }
         }
-    }
-    fn reorder_char(&mut self, char: &mut Character) {
-        for layer in &self.entities {
-            for entity in layer {
-                
-                let mut entity_hitbox = entity.get_hitbox();
-                entity_hitbox.x += self.x;
-                entity_hitbox.y += self.y;
-                let entity_z_index = entity.get_z_index();
-                if entity_hitbox.top_left().y() > char.rep.hitbox.top_left().y() && entity_z_index < char.rep.z_index { //if char is above (visually) the entity
-                    //println!("{}, {}", char.rep.z_index, entity.z_index);
-                    //println!("yoo");
-                    char.rep.z_index = entity_z_index; //because char will always be the lowest in the layer
-                } else if entity_hitbox.top_left().y() < char.rep.hitbox.top_left().y() {
-                    char.rep.z_index = entity_z_index + 1;
-                    
-                    //println!("zoo");
-                } //char is below the entity
-
+        hits
+    }
+    //the interaction the character started this tick
+    fn check_interact(&self, char: &Character, controls: &Controls) -> Option<Interaction<'a>> {
+        for (i, _) in self.zones.query(char.rep.hitbox) {
+            let act = &self.interactables[i];
+            if act.check(char.rep.hitbox) {
+                if let Some(x) = act.exec(controls) {
+                    return Some(x)
+                }
             }
         }
+        None
     }
-    fn check_interact(&mut self, char: &Character, keyboard: &KeyboardState, canvas: &mut WindowCanvas) -> Option<(fn() -> bool, u8)> {
-        let mut i = 0;
-        for act in &self.interactables {
-            if act.check(self.x, self.y, char.rep.hitbox) {
-                self.present_interact(canvas, act.hitbox).unwrap();
-                if let Some(x) = act.exec(keyboard) {
-                    return Some((x, i))
-                }
+    //the conversation of an entity next to the character, if the character started talking this tick
+    fn check_talk(&self, char: &Character, controls: &Controls) -> Option<String> {
+        if !controls.pressed(Action::Interact) {
+            return None
+        }
+        let hitbox = char.rep.hitbox;
+        let reach = Rect::new(hitbox.x() - 40, hitbox.y() - 40, hitbox.width() + 80, hitbox.height() + 80);
+        self.bodies.query(reach).into_iter()
+            .find_map(|((z, i), _)| self.entities[z][i].dialogue().map(String::from))
+    }
+    //the prompts over the interactables the character is standing in
+    fn present_interactions(&self, canvas: &mut WindowCanvas, char: &Character) -> Result<(), String> {
+        for (i, _) in self.zones.query(char.rep.hitbox) {
+            let act = &self.interactables[i];
+            if act.check(char.rep.hitbox) {
+                self.present_interact(canvas, act.hitbox)?;
             }
         }
-        None
+        Ok(())
     }
     fn present(&self, canvas: &mut WindowCanvas, char: &Character) -> Result<(), String> {
-        //self.tiles.present(canvas, self.x, self.y)?;
-        self.map.base.present(canvas, self.x, self.y)?;
-        let mut i = 0;
-        for layer in &self.entities {
-            if char.rep.z_index == i {
-                char.rep.present(canvas, 0, 0)?;
+        let (x, y) = self.camera.offset();
+        //zooming on top of the scaling of the logical screen
+        let (scale_x, scale_y) = canvas.scale();
+        canvas.set_scale(scale_x * self.camera.zoom(), scale_y * self.camera.zoom())?;
+        self.tiles.present(canvas, self.camera.view(), x, y)?;
+        //the character is drawn between its last two positions, like the camera
+        let (prev, pixel) = (char.prev_pixel(), char.pixel());
+        let char_x = x + self.camera.between(prev.x(), pixel.x()) - pixel.x();
+        let char_y = y + self.camera.between(prev.y(), pixel.y()) - pixel.y();
+        //only the entities on the screen, with a margin for whatever they draw around their bodies
+        let view = self.camera.view();
+        let view = Rect::new(view.x() - 64, view.y() - 64, view.width() + 128, view.height() + 128);
+        let mut visible = self.bodies.query(view).into_iter().peekable();
+        for z in 0..self.entities.len() {
+            //within a layer whatever has its feet lower is in front, None is the character
+            let mut drawables: Vec<(i32, Option<usize>)> = vec![];
+            while let Some(((_, i), _)) = visible.next_if(|((layer, _), _)| *layer == z) {
+                drawables.push((self.entities[z][i].get_hitbox().bottom(), Some(i)));
+            }
+            if char.rep.layer as usize == z && char.health.visible() {
+                drawables.push((char.rep.hitbox.bottom(), None));
             }
-            i += 1;
-            for entity in layer {
-                entity.present(canvas, self.x, self.y)?;
+            //stable, so on a tie the character is in front
+            drawables.sort_by_key(|(feet, _)| *feet);
+            for (_, drawable) in drawables {
+                match drawable {
+                    Some(i) => self.entities[z][i].present(canvas, x, y)?,
+                    None => char.rep.present(canvas, char_x, char_y)?,
+                }
                 //canvas.fill_rect(hitbox)?;
             }
         }
-        Ok(())
+        canvas.set_scale(scale_x, scale_y)
     }
     fn present_interact(&self, canvas: &mut WindowCanvas, other: Rect) -> Result<(), String> {
+        let (x, y) = self.camera.offset();
+        let (scale_x, scale_y) = canvas.scale();
+        canvas.set_scale(scale_x * self.camera.zoom(), scale_y * self.camera.zoom())?;
         self.e.present(canvas, 
-            self.x + other.x() + (other.width() / 2) as i32, 
-            self.y + other.y() + (other.height() / 2) as i32)?;
-        Ok(())
+            x + other.x() + (other.width() / 2) as i32, 
+            y + other.y() + (other.height() / 2) as i32)?;
+        canvas.set_scale(scale_x, scale_y)
     }
 
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 enum Direction {
     Left = 1,
     Right = 2,
     Up = 3,
     Down = 4,
 }
+impl Direction {
+    //the unit vector pointing in the direction, y grows downwards
+    fn vector(self) -> (i32, i32) {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+        }
+    }
+}
+impl FromStr for Direction {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Direction::Left),
+            "right" => Ok(Direction::Right),
+            "up" => Ok(Direction::Up),
+            "down" => Ok(Direction::Down),
+            _ => Err(format!("unknown direction '{}'", s))
+        }
+    }
+}
+//the way the character faces, diagonals included
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum Facing {
+    Left,
+    UpLeft,
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+}
+impl Facing {
+    //the closest of the eight to a vector, y grows downwards
+    fn from_vector(x: f32, y: f32) -> Self {
+        match ((y.atan2(x) / FRAC_PI_4).round() as i32).rem_euclid(8) {
+            0 => Facing::Right,
+            1 => Facing::DownRight,
+            2 => Facing::Down,
+            3 => Facing::DownLeft,
+            4 => Facing::Left,
+            5 => Facing::UpLeft,
+            6 => Facing::Up,
+            _ => Facing::UpRight,
+        }
+    }
+    //the four-way direction of the animation to use, diagonals keep `current` if it's one of their sides
+    //so turning from up to up-right doesn't flip the sprite
+    fn direction(self, current: Direction) -> Direction {
+        let sides = match self {
+            Facing::Left => [Direction::Left; 2],
+            Facing::UpLeft => [Direction::Left, Direction::Up],
+            Facing::Up => [Direction::Up; 2],
+            Facing::UpRight => [Direction::Right, Direction::Up],
+            Facing::Right => [Direction::Right; 2],
+            Facing::DownRight => [Direction::Right, Direction::Down],
+            Facing::Down => [Direction::Down; 2],
+            Facing::DownLeft => [Direction::Left, Direction::Down],
+        };
+        if sides.contains(&current) {current} else {sides[0]}
+    }
+}
+//the layers of the world, drawn in this order
+#[derive(serde::Deserialize, PartialEq, Eq, Copy, Clone, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+enum Layer {
+    Ground, //flat things everything walks over
+    #[default]
+    Objects, //the character, NPCs, trees, sorted by their feet
+    Overhead, //things in front of everything, e.g. treetops
+}
+impl Layer {
+    const ALL: [Layer; 3] = [Layer::Ground, Layer::Objects, Layer::Overhead];
+}
+//the key an entity's animation is registered under
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+enum Anim {
+    Idle,
+    Walk(Direction),
+    Dash(Direction),
+    Attack(Direction),
+    Death,
+}
+impl FromStr for Anim {
+    type Err = String;
+    //"idle", "death" or "<walk|dash|attack>_<direction>", e.g. "dash_left"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "idle" => return Ok(Anim::Idle),
+            "death" => return Ok(Anim::Death),
+            _ => {}
+        }
+        let (kind, dir) = s.split_once('_').ok_or(format!("unknown animation key '{}'", s))?;
+        let dir = dir.parse()?;
+        match kind {
+            "walk" => Ok(Anim::Walk(dir)),
+            "dash" => Ok(Anim::Dash(dir)),
+            "attack" => Ok(Anim::Attack(dir)),
+            _ => Err(format!("unknown animation key '{}'", s))
+        }
+    }
+}
 struct Character<'a> {
-    x: i32, //x in the world's coordinate system
-    y: i32, //y in the world's coordinate system
-    rep: Entity<'a>, //the character's representation: an entity
-    dir: Direction, //the direction the character is facing
-    speed: i32,
-    dodge_cooldown: Instant
+    x: f32, //the middle of the character in the world's coordinate system, the fraction is kept between ticks
+    y: f32,
+    prev_x: f32, //x before the current tick, used for interpolating
+    prev_y: f32,
+    rep: Entity<'a>, //the character's representation: an entity, always on whole pixels
+    dir: Direction, //the direction of the character's animations
+    facing: Facing, //the direction the character is facing
+    velocity: (f32, f32), //pixels per second on both axes
+    speed: i32, //pixels per second
+    step: i32, //the most pixels the character can move this tick
+    dodge_cooldown: Duration, //time since the last dash
+    health: Health,
 }
 impl<'a> Character<'a> {
-    fn move_world(&mut self, dir: Direction, part: &mut i32) {
-        *part += self.speed * ((-1i32).pow(dir as u32 + 1 % 2));
-        self.dir = dir;
-        self.rep.switch_to(dir as usize);
-    }
-    fn speed(&mut self, keyboard: &KeyboardState, dash: &mut Entity<'a>) -> bool {
-        if keyboard.is_scancode_pressed(Scancode::LShift) {
-            self.speed = 5;
+    //putting the character somewhere in the world, e.g. on a spawn point
+    fn place(&mut self, point: Point) {
+        let pixel = self.pixel();
+        self.rep.offset_x(point.x() - pixel.x());
+        self.rep.offset_y(point.y() - pixel.y());
+        self.x = point.x() as f32;
+        self.y = point.y() as f32;
+        self.snapshot();
+    }
+    //the position rounded to the pixel the character is drawn at
+    fn pixel(&self) -> Point {
+        Point::new(self.x.round() as i32, self.y.round() as i32)
+    }
+    fn prev_pixel(&self) -> Point {
+        Point::new(self.prev_x.round() as i32, self.prev_y.round() as i32)
+    }
+    //remembering where the character was before a tick moves it
+    fn snapshot(&mut self) {
+        self.prev_x = self.x;
+        self.prev_y = self.y;
+    }
+    fn speed(&mut self, controls: &Controls, dash: &mut Entity<'a>, dt: Duration) -> Result<bool, String> {
+        self.dodge_cooldown += dt;
+        if controls.held(Action::Sprint) {
+            self.speed = 300;
         } else {
-            self.speed = 3
+            self.speed = 180
         }
-        if self.dodge_cooldown.elapsed() < Duration::from_millis(150) {
-            self.speed = 18;
+        if self.dodge_cooldown < Duration::from_millis(150) {
+            self.speed = 1080;
         }
         //checking for dash
-        if keyboard.is_scancode_pressed(Scancode::LAlt) {
-            if self.dodge_cooldown.elapsed() > Duration::from_secs(2) {
-                let anim = self.dir as usize + 5;
-                self.rep.play(Some(anim));
-                self.speed = 20;
-                dash.play(None);
-                self.dodge_cooldown = Instant::now();
-                return true
+        if controls.held(Action::Dash) {
+            if self.dodge_cooldown > Duration::from_secs(2) {
+                self.rep.play(Some(Anim::Dash(self.dir)))?;
+                self.speed = 1200;
+                dash.play(None)?;
+                self.dodge_cooldown = Duration::ZERO;
+                return Ok(true)
             }
         }
-        false
-    }
-    fn movement(&mut self, keyboard: &KeyboardState, world: &mut World) -> bool {
-        let mut moved = false;
-        if keyboard.is_scancode_pressed(Scancode::D) {
-            if world.can_move(self, Direction::Right) {
-                self.move_world(Direction::Right, &mut world.x);
-                moved = true;
-            }
+        Ok(false)
+    }
+    //the area hit by the current attack and the direction of the attack, only during the attack's active frames
+    fn attack_hitbox(&self) -> Option<(Rect, Direction)> {
+        let dir = [Direction::Left, Direction::Right, Direction::Up, Direction::Down]
+            .into_iter()
+            .find(|dir| self.rep.is_playing(Anim::Attack(*dir)))?;
+        if !self.rep.current().is_hitting() {
+            return None
         }
-        else if keyboard.is_scancode_pressed(Scancode::A) {
-            if world.can_move(self, Direction::Left) {
-                self.move_world(Direction::Left, &mut world.x);
-                moved = true;
-            }
+        let body = self.rep.dst;
+        let reach = 40;
+        let area = match dir {
+            Direction::Left => Rect::new(body.x() - reach, body.y(), body.width() / 2 + reach as u32, body.height()),
+            Direction::Right => Rect::new(body.center().x(), body.y(), body.width() / 2 + reach as u32, body.height()),
+            Direction::Up => Rect::new(body.x(), body.y() - reach, body.width(), body.height() / 2 + reach as u32),
+            Direction::Down => Rect::new(body.x(), body.center().y(), body.width(), body.height() / 2 + reach as u32),
+        };
+        Some((area, dir))
+    }
+    fn movement(&mut self, controls: &Controls, world: &World, dt: Duration) -> Result<bool, String> {
+        self.step = step(self.speed, dt);
+        //opposite keys cancel out, a stick can be tilted only a bit
+        let x = controls.value(Action::MoveRight) - controls.value(Action::MoveLeft);
+        let y = controls.value(Action::MoveDown) - controls.value(Action::MoveUp);
+        let length = x.hypot(y);
+        if length < 0.01 {
+            self.velocity = (0.0, 0.0);
+            return Ok(false)
         }
-        if keyboard.is_scancode_pressed(Scancode::W) {
-            if world.can_move(self, Direction::Up) {
-                self.move_world(Direction::Up, &mut world.y);
-                moved = true;
-            }
+        //normalized so diagonals aren't faster, but a half tilted stick still walks slower
+        let scale = self.speed as f32 * length.min(1.0) / length;
+        self.velocity = (x * scale, y * scale);
+        self.facing = Facing::from_vector(x, y);
+
+        //only whole pixels move the hitbox, the fraction adds up over the next ticks
+        let secs = dt.as_secs_f32();
+        let (to_x, to_y) = (self.x + self.velocity.0 * secs, self.y + self.velocity.1 * secs);
+        let from = self.pixel();
+        let motion = world.slide(self.rep.hitbox, to_x.round() as i32 - from.x(), to_y.round() as i32 - from.y());
+        self.rep.offset_x(motion.dx);
+        self.rep.offset_y(motion.dy);
+        //a wall stops the character on the pixel next to it, sliding along on the other axis
+        if motion.normal.0 != 0 {
+            self.x = (from.x() + motion.dx) as f32;
+            self.velocity.0 = 0.0;
+        } else {
+            self.x = to_x;
         }
-        else if keyboard.is_scancode_pressed(Scancode::S) {
-            if world.can_move(self, Direction::Down) {
-                self.move_world(Direction::Down, &mut world.y);
-                moved = true;
-            }
+        if motion.normal.1 != 0 {
+            self.y = (from.y() + motion.dy) as f32;
+            self.velocity.1 = 0.0;
+        } else {
+            self.y = to_y;
+        }
+        if self.velocity == (0.0, 0.0) {
+            return Ok(false)
         }
-        moved
+        self.dir = self.facing.direction(self.dir);
+        self.rep.switch_to(Anim::Walk(self.dir))?;
+        Ok(true)
     }
 }
+#[derive(Clone)]
 struct Entity<'a> {
     x: i32, //x in the coordinate system of the world
     y: i32, //y in the coordinate system of the world
@@ -945,8 +1029,9 @@ struct Entity<'a> {
     active: usize, //the number of the active animation from the animations list
     last: usize, //the number of the previous active animation
     animations: Vec<Animation<'a>>, //a list containing the entity's animations
+    slots: HashMap<Anim, usize>, //the index of each registered animation in the list
     dst: Rect, //the destination where SDL2 will put the entity on the screen
-    z_index: u8, //the layer number
+    layer: Layer,
     hitbox: Rect,
 } 
 impl<'a> Entity<'a> {
@@ -963,8 +1048,9 @@ impl<'a> Entity<'a> {
             active: 0,
             last: 0,
             animations: vec![base],
+            slots: HashMap::from([(Anim::Idle, 0)]),
             dst,
-            z_index: 0,
+            layer: Layer::Objects,
             hitbox: dst,
         }
     }
@@ -988,9 +1074,10 @@ impl<'a> Entity<'a> {
         self.hitbox.h = (self.hitbox.h as f32 * value) as i32;
         self.dst()
     }
-    fn center(&mut self) {
-        self.x = (SCREEN_WIDTH as i32 - self.w as i32) / 2;
-        self.y = (SCREEN_HEIGHT as i32 - self.h as i32) / 2;
+    fn center(&mut self, display: &Display) {
+        let corner = display.center(self.w, self.h);
+        self.x = corner.x();
+        self.y = corner.y();
         self.dst.x = self.x;
         self.dst.y = self.y;
     }
@@ -999,35 +1086,63 @@ impl<'a> Entity<'a> {
         let y = ((SCREEN_HEIGHT as i32 - self.h as i32) / 2) + self.y;
         self.dst = Rect::new(x, y, self.w, self.h);
     }
-    fn switch_to(&mut self, num: usize) {
+    fn add_animation(&mut self, key: Anim, anim: Animation<'a>) {
+        match self.slots.get(&key) {
+            Some(&num) => self.animations[num] = anim,
+            None => {
+                self.slots.insert(key, self.animations.len());
+                self.animations.push(anim);
+            }
+        }
+    }
+    fn slot(&self, key: Anim) -> Result<usize, String> {
+        self.slots.get(&key).copied().ok_or(format!("entity has no {:?} animation", key))
+    }
+    fn has(&self, key: Anim) -> bool {
+        self.slots.contains_key(&key)
+    }
+    fn is_playing(&self, key: Anim) -> bool {
+        self.slots.get(&key) == Some(&self.active)
+    }
+    fn current(&self) -> &Animation<'a> {
+        &self.animations[self.active]
+    }
+    fn switch_to(&mut self, key: Anim) -> Result<(), String> {
+        let num = self.slot(key)?;
         if self.active != num && self.animations[self.active].interruptable {
             self.last = self.active;
             self.active = num;
         }
+        Ok(())
     }
-    fn play(&mut self, num: Option<usize>) {
-        match num {
-            Some(x) => {
-                self.switch_to(x)
-            }
-            None => {()}
+    fn play(&mut self, key: Option<Anim>) -> Result<(), String> {
+        if let Some(key) = key {
+            self.switch_to(key)?;
         }
         self.animations[self.active].ongoing = true;
+        Ok(())
     }
     fn force_switch(&mut self, num: usize) {
         self.active = num;
     }
-    fn next(&mut self) {
-        if self.animations[self.active].next() {
-            self.force_switch(self.last);
+    //playing an animation from its first frame even if the current one can't be interrupted
+    fn force_play(&mut self, key: Anim) -> Result<(), String> {
+        let num = self.slot(key)?;
+        if self.active != num {
+            self.last = self.active;
+            self.force_switch(num);
         }
+        self.rewind();
+        self.animations[self.active].ongoing = true;
+        Ok(())
     }
-    fn clone(&self, loader: &'a TextureCreator<WindowContext>) -> Self {
-        let mut animations = Vec::new();
-        for elem in &self.animations {
-            animations.push(elem.clone(loader))
+    fn rewind(&mut self) {
+        self.animations[self.active].rewind();
+    }
+    fn next(&mut self, dt: Duration) {
+        if self.animations[self.active].next(dt) {
+            self.force_switch(self.last);
         }
-        Entity { x: self.x, y: self.y, w: self.w, h: self.h, active: self.active, last: self.last, animations, dst: self.dst, z_index: self.z_index, hitbox: self.hitbox }
     }
     fn gen_hitbox(&mut self, hitbox: Rect) {
         let real_hitbox = Rect::new(
@@ -1041,8 +1156,9 @@ impl<'a> Entity<'a> {
     }
 }
 
+#[derive(Clone)]
 struct Animation<'a> {
-    sheet: Texture<'a>, //the sheet from which the frames of the animation are sourced
+    sheet: Rc<Texture<'a>>, //the sheet from which the frames of the animation are sourced, shared between clones
     source: String, //the path to the image file containing the sheet
     rows: u8, //the number of rows (frames) on the sheet
     cols: u8, //the no. of cols (frames) on the sheet
@@ -1052,10 +1168,11 @@ struct Animation<'a> {
     total: usize, //the length of the sequence
     ongoing: bool, //is the animation ongoing
     dur: Duration, //the amount of time that needs to elapse between frames
-    elapse: Instant, //stopper-watch used to measure the elapse of time
+    elapsed: Duration, //the simulated time spent on the current frame
     interruptable: bool, //can the animation be interrupted by another animation
     looped: bool, //is the animation on a loop
     movable: bool, //can the entity move while displaying this animation
+    hits: Option<(usize, usize)>, //the first and last frame during which an attack animation deals damage
 } 
 impl<'a> Animation<'a> {
     fn get_units(&self) -> (u32, u32) {
@@ -1075,15 +1192,19 @@ impl<'a> Animation<'a> {
             Rect::new(i as i32 * unit_w as i32, j as i32 * unit_h as i32, unit_w, unit_h)
         }
     }
-    fn new(filename: &str, rows: u8, cols: u8, frames: Vec<(u8, u8)>, loader: &'a TextureCreator<WindowContext>) -> Self {
+    fn new(filename: &str, rows: u8, cols: u8, frames: Vec<(u8, u8)>, textures: &TextureCache<'a>) -> Result<Self, String> {
+        let sheet = textures.load(filename)?;
+        if frames.is_empty() {
+            return Err(format!("{}: an animation needs at least one frame", filename))
+        }
         let total = frames.len() - 1;
         let mut frames_new = vec![];
         for frame in frames {
             let frame_new = (frame.0, frame.1, None);
             frames_new.push(frame_new);
         }
-        Animation {
-            sheet: loader.load_texture(filename).unwrap(), 
+        Ok(Animation {
+            sheet,
             source: filename.to_string(),
             rows, 
             cols, 
@@ -1093,11 +1214,12 @@ impl<'a> Animation<'a> {
             total, 
             ongoing: true, 
             dur: Duration::from_millis(100), 
-            elapse: Instant::now(),
+            elapsed: Duration::ZERO,
             interruptable: true,
             looped: true,
             movable: true,
-        }
+            hits: None,
+        })
     }
     fn from_texture(texture: Texture<'a>, rows: u8, cols: u8, frames: Vec<(u8, u8)>) -> Self {
         let total = frames.len() - 1;
@@ -1107,7 +1229,7 @@ impl<'a> Animation<'a> {
             frames_new.push(frame_new);
         }
         Animation {
-            sheet: texture, 
+            sheet: Rc::new(texture), 
             source: "".to_string(),
             rows, 
             cols, 
@@ -1117,17 +1239,32 @@ impl<'a> Animation<'a> {
             total, 
             ongoing: true, 
             dur: Duration::from_millis(100), 
-            elapse: Instant::now(),
+            elapsed: Duration::ZERO,
             interruptable: true,
             looped: true,
             movable: true,
+            hits: None,
+        }
+    }
+    fn is_hitting(&self) -> bool {
+        match self.hits {
+            Some((first, last)) => self.ongoing && first <= self.current && self.current <= last,
+            None => false
         }
     }
-    fn next(&mut self) -> bool {
-        if self.elapse.elapsed() < self.dur {
+    fn rewind(&mut self) {
+        self.current = 0;
+        self.current_frame = self.frames[0];
+        self.elapsed = Duration::ZERO;
+    }
+    fn next(&mut self, dt: Duration) -> bool {
+        self.elapsed += dt;
+        if self.elapsed < self.dur {
             return false
         }
         if !self.ongoing {
+            //not piling up while stopped, so it doesn't race through the frames when played again
+            self.elapsed = self.dur;
             return true
         }
         if self.current == self.total {
@@ -1142,27 +1279,10 @@ impl<'a> Animation<'a> {
         }
 
         self.current_frame = self.frames[self.current];
-        self.elapse = Instant::now();
+        //keeping what is left over, so the speed doesn't depend on the length of the ticks
+        self.elapsed -= self.dur;
         return false       
     }
-    fn clone(&self, loader: &'a TextureCreator<WindowContext>) -> Self {
-        Animation {
-            sheet: loader.load_texture(self.source.clone()).unwrap(),
-            source: self.source.clone(), 
-            rows: self.rows, 
-            cols: self.cols, 
-            current_frame: self.current_frame, 
-            current: self.current, 
-            frames: self.frames.clone(), 
-            total: self.total, 
-            ongoing: self.ongoing, 
-            dur: self.dur, 
-            elapse: self.elapse, 
-            interruptable: self.interruptable, 
-            looped: self.looped,
-            movable: self.movable
-        }
-    }
 }
 
 trait Sized {
@@ -0,0 +1,237 @@
+use std::{collections::HashMap, fs, time::Duration};
+use sdl2::rect::Rect;
+use serde::Deserialize;
+
+use crate::{assets::TextureCache, enemy::{Enemy, EnemyStats}, Anim, Animation, Entity, Layer};
+
+#[derive(Deserialize)]
+pub struct AnimationDef {
+    sheet: String, //the path to the image file containing the sheet
+    rows: u8,
+    cols: u8,
+    frames: Vec<(u8, u8)>, //(col, row) coordinates of the frames
+    #[serde(default = "default_dur")]
+    dur: u64, //milliseconds between frames
+    src: Option<(i32, i32, u32, u32)>, //a rect cut from the sheet instead of the frame grid
+    #[serde(default = "yes")]
+    interruptable: bool,
+    #[serde(default = "yes")]
+    looped: bool,
+    #[serde(default = "yes")]
+    movable: bool,
+    #[serde(default = "yes")]
+    ongoing: bool,
+    hits: Option<(usize, usize)>, //the first and last frame of an attack that deal damage
+}
+fn default_dur() -> u64 {
+    100
+}
+fn yes() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub struct EntityDef {
+    animations: HashMap<String, String>, //animation key (e.g. "walk_left") -> animation name, "idle" is the default
+    size: Option<(u32, u32)>,
+    scale: Option<(f32, f32)>,
+    #[serde(default)]
+    layer: Layer,
+    hitbox: Option<(i32, i32, u32, u32)>, //relative to the entity's top left corner
+}
+
+//a set of named animations and entities read from a file, see assets/animations.toml
+#[derive(Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    animations: HashMap<String, AnimationDef>,
+    #[serde(default)]
+    entities: HashMap<String, EntityDef>,
+    #[serde(default)]
+    enemies: HashMap<String, EnemyStats>,
+}
+impl Manifest {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Manifest::parse(&content).map_err(|e| format!("{}: {}", path, e))
+    }
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let manifest: Manifest = toml::from_str(content).map_err(|e| e.to_string())?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+    //catching mistakes in the file before any texture is loaded
+    fn validate(&self) -> Result<(), String> {
+        for (name, def) in &self.animations {
+            if def.frames.is_empty() {
+                return Err(format!("animation '{}' has no frames", name));
+            }
+            if def.rows == 0 || def.cols == 0 {
+                return Err(format!("animation '{}' needs at least one row and column", name));
+            }
+            if let Some(&(col, row)) = def.frames.iter().find(|(col, row)| *col >= def.cols || *row >= def.rows) {
+                return Err(format!("animation '{}' has frame ({}, {}) outside of its {}x{} sheet", name, col, row, def.cols, def.rows));
+            }
+            if let Some((first, last)) = def.hits {
+                if first > last || last >= def.frames.len() {
+                    return Err(format!("animation '{}' hits on frames {}-{}, but it has {} frames", name, first, last, def.frames.len()));
+                }
+            }
+        }
+        for (name, def) in &self.entities {
+            if !def.animations.contains_key("idle") {
+                return Err(format!("entity '{}' has no idle animation", name));
+            }
+            for (key, anim) in &def.animations {
+                key.parse::<Anim>().map_err(|e| format!("entity '{}': {}", name, e))?;
+                if !self.animations.contains_key(anim) {
+                    return Err(format!("entity '{}' uses unknown animation '{}'", name, anim));
+                }
+            }
+        }
+        for (name, stats) in &self.enemies {
+            if !self.entities.contains_key(&stats.entity) {
+                return Err(format!("enemy '{}' uses unknown entity '{}'", name, stats.entity));
+            }
+        }
+        Ok(())
+    }
+    pub fn animation<'a>(&self, name: &str, textures: &TextureCache<'a>) -> Result<Animation<'a>, String> {
+        let def = self.animations.get(name).ok_or(format!("no animation named '{}'", name))?;
+        let mut anim = Animation::new(&def.sheet, def.rows, def.cols, def.frames.clone(), textures)?;
+        anim.dur = Duration::from_millis(def.dur);
+        anim.interruptable = def.interruptable;
+        anim.looped = def.looped;
+        anim.movable = def.movable;
+        anim.ongoing = def.ongoing;
+        anim.hits = def.hits;
+        if let Some((x, y, w, h)) = def.src {
+            let src = Some(Rect::new(x, y, w, h));
+            for frame in &mut anim.frames {
+                frame.2 = src;
+            }
+            anim.current_frame.2 = src;
+        }
+        Ok(anim)
+    }
+    pub fn entity<'a>(&self, name: &str, x: i32, y: i32, textures: &TextureCache<'a>) -> Result<Entity<'a>, String> {
+        let def = self.entities.get(name).ok_or(format!("no entity named '{}'", name))?;
+        let mut entity = Entity::from(self.animation(&def.animations["idle"], textures)?, x, y);
+        for (key, anim) in &def.animations {
+            if key != "idle" {
+                entity.add_animation(key.parse()?, self.animation(anim, textures)?);
+            }
+        }
+        if let Some((w, h)) = def.size {
+            entity.w = w;
+            entity.h = h;
+        }
+        if let Some((w, h)) = def.scale {
+            entity.mult_w(w);
+            entity.mult_h(h);
+        }
+        entity.dst();
+        entity.layer = def.layer;
+        if let Some((x, y, w, h)) = def.hitbox {
+            entity.gen_hitbox(Rect::new(x, y, w, h));
+        }
+        Ok(entity)
+    }
+    pub fn enemy<'a>(&self, name: &str, x: i32, y: i32, textures: &TextureCache<'a>) -> Result<Enemy<'a>, String> {
+        let stats = self.enemies.get(name).ok_or(format!("no enemy named '{}'", name))?;
+        let entity = self.entity(&stats.entity, x, y, textures)?;
+        Ok(Enemy::from(stats.clone(), entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALK: &str = r#"
+[animations.walk]
+sheet = "walk.png"
+rows = 2
+cols = 4
+frames = [[0, 0], [1, 0], [3, 1]]
+hits = [1, 2]
+"#;
+
+    fn error(content: &str) -> String {
+        match Manifest::parse(content) {
+            Ok(_) => panic!("the manifest should be rejected"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn valid_manifest_parses() {
+        let content = format!(r#"{}
+[entities.guy]
+animations = {{ idle = "walk", walk_left = "walk", attack_up = "walk" }}
+layer = "overhead"
+
+[enemies.grunt]
+entity = "guy"
+hp = 3
+speed = 50
+chase_speed = 90
+sight = 200
+damage = 1
+cooldown = 800
+"#, WALK);
+        let manifest = Manifest::parse(&content).unwrap();
+        assert_eq!(manifest.animations["walk"].dur, 100);
+        assert!(manifest.animations["walk"].looped);
+        assert_eq!(manifest.entities["guy"].layer, Layer::Overhead);
+        assert!(Manifest::parse("").is_ok());
+    }
+
+    #[test]
+    fn frames_must_be_on_the_sheet() {
+        assert!(error(&WALK.replace("[3, 1]", "[4, 0]")).contains("outside of its 4x2 sheet"));
+        assert!(error(&WALK.replace("rows = 2", "rows = 0")).contains("at least one row"));
+        assert!(error(&WALK.replace("frames = [[0, 0], [1, 0], [3, 1]]", "frames = []")).contains("no frames"));
+    }
+
+    #[test]
+    fn hits_must_be_frames() {
+        assert!(error(&WALK.replace("hits = [1, 2]", "hits = [1, 3]")).contains("hits on frames 1-3"));
+        assert!(error(&WALK.replace("hits = [1, 2]", "hits = [2, 1]")).contains("hits on frames 2-1"));
+    }
+
+    #[test]
+    fn entities_need_known_animations() {
+        let entity = |animations: &str| format!("{}\n[entities.guy]\nanimations = {{ {} }}\n", WALK, animations);
+        assert!(Manifest::parse(&entity(r#"idle = "walk""#)).is_ok());
+        assert!(error(&entity(r#"walk_left = "walk""#)).contains("no idle animation"));
+        assert!(error(&entity(r#"idle = "run""#)).contains("unknown animation 'run'"));
+        assert!(error(&entity(r#"idle = "walk", walk_sideways = "walk""#)).contains("entity 'guy'"));
+        assert!(error(&entity(r#"idle = "walk", jump_left = "walk""#)).contains("unknown animation key 'jump_left'"));
+    }
+
+    #[test]
+    fn enemies_need_known_entities() {
+        let content = r#"
+[enemies.grunt]
+entity = "nobody"
+hp = 3
+speed = 50
+chase_speed = 90
+sight = 200
+damage = 1
+cooldown = 800
+"#;
+        assert!(error(content).contains("unknown entity 'nobody'"));
+    }
+
+    #[test]
+    fn the_game_manifest_is_valid() {
+        Manifest::load("assets/animations.toml").unwrap();
+    }
+
+    #[test]
+    fn missing_fields_are_errors() {
+        assert!(Manifest::parse("[animations.walk]\nsheet = \"walk.png\"").is_err());
+    }
+}
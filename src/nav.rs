@@ -0,0 +1,246 @@
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}, time::Duration};
+use sdl2::rect::{Point, Rect};
+
+use crate::Direction;
+
+type Cell = (i32, i32); //(col, row) on the grid
+
+//a grid over the map telling where NPCs can walk, in the coordinate system of the entities' hitboxes
+pub struct NavGrid {
+    bounds: Rect, //the area covered by the grid
+    cell: u32, //the size of one cell in pixels
+    cols: i32,
+    rows: i32,
+    blocked: Vec<bool>,
+    margin: i32, //obstacles are grown by this much so NPCs don't clip corners
+}
+impl NavGrid {
+    pub fn new(bounds: Rect, cell: u32, margin: i32) -> Self {
+        let cols = (bounds.width() as f32 / cell as f32).ceil() as i32;
+        let rows = (bounds.height() as f32 / cell as f32).ceil() as i32;
+        NavGrid { bounds, cell, cols, rows, blocked: vec![false; (cols * rows) as usize], margin }
+    }
+    //marking every cell an obstacle touches as blocked
+    pub fn block(&mut self, obstacle: Rect) {
+        if obstacle.width() == 0 || obstacle.height() == 0 {
+            return
+        }
+        let grown = Rect::new(
+            obstacle.x() - self.margin,
+            obstacle.y() - self.margin,
+            obstacle.width() + 2 * self.margin as u32,
+            obstacle.height() + 2 * self.margin as u32);
+        let (left, top) = self.cell_at(grown.top_left());
+        let (right, bottom) = self.cell_at(Point::new(grown.right() - 1, grown.bottom() - 1));
+        for row in top.max(0)..=bottom.min(self.rows - 1) {
+            for col in left.max(0)..=right.min(self.cols - 1) {
+                self.blocked[(row * self.cols + col) as usize] = true;
+            }
+        }
+    }
+    //marking the edges of a map hitbox as blocked, the inside is free since hitboxes can be in it like in a room (see ComplexHitbox::blocks)
+    pub fn wall(&mut self, area: Rect) {
+        let (x, y, w, h) = (area.x(), area.y(), area.width(), area.height());
+        self.block(Rect::new(x, y, w, 1));
+        self.block(Rect::new(x, area.bottom() - 1, w, 1));
+        self.block(Rect::new(x, y, 1, h));
+        self.block(Rect::new(area.right() - 1, y, 1, h));
+    }
+    fn cell_at(&self, point: Point) -> Cell {
+        let size = self.cell as f32;
+        (
+            ((point.x() - self.bounds.x()) as f32 / size).floor() as i32,
+            ((point.y() - self.bounds.y()) as f32 / size).floor() as i32,
+        )
+    }
+    fn center_of(&self, (col, row): Cell) -> Point {
+        let size = self.cell as i32;
+        Point::new(self.bounds.x() + col * size + size / 2, self.bounds.y() + row * size + size / 2)
+    }
+    fn is_free(&self, (col, row): Cell) -> bool {
+        col >= 0 && row >= 0 && col < self.cols && row < self.rows && !self.blocked[(row * self.cols + col) as usize]
+    }
+    //A* over the free cells, returns the waypoints from `from` to `to` or None if there is no way (within `budget` steps)
+    pub fn find_path(&self, from: Point, to: Point, budget: usize) -> Option<Vec<Point>> {
+        let start = self.cell_at(from);
+        let goal = self.cell_at(to);
+        if !self.is_free(goal) {
+            return None
+        }
+        let heuristic = |(col, row): Cell| (col - goal.0).abs() + (row - goal.1).abs();
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+        let mut cost: HashMap<Cell, i32> = HashMap::from([(start, 0)]);
+        open.push(Reverse((heuristic(start), start)));
+
+        let mut expanded = 0;
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                //walking back from the goal
+                let mut path = vec![to];
+                let mut cell = current;
+                while let Some(&previous) = came_from.get(&cell) {
+                    if previous != start {
+                        path.push(self.center_of(previous));
+                    }
+                    cell = previous;
+                }
+                path.reverse();
+                return Some(path)
+            }
+            expanded += 1;
+            if expanded > budget {
+                return None
+            }
+            for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let next = (current.0 + dx, current.1 + dy);
+                if !self.is_free(next) {
+                    continue
+                }
+                let new_cost = cost[&current] + 1;
+                if cost.get(&next).map_or(true, |&old| new_cost < old) {
+                    cost.insert(next, new_cost);
+                    came_from.insert(next, current);
+                    open.push(Reverse((new_cost + heuristic(next), next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+//follows a path to a target, asking the grid for a new one at most every `replan` so many NPCs stay cheap
+pub struct Navigator {
+    path: Vec<Point>, //the remaining waypoints, the next one first
+    goal: Option<Point>, //the target the path was planned for
+    wait: Duration, //time until the next path request is allowed
+    replan: Duration,
+}
+impl Navigator {
+    pub fn new(replan: Duration) -> Self {
+        Navigator { path: vec![], goal: None, wait: Duration::ZERO, replan }
+    }
+    //the direction to walk from `pos` to get to `target`, None if the grid knows no way there
+    pub fn direction(&mut self, grid: &NavGrid, pos: Point, target: Point, step: i32, dt: Duration) -> Option<Direction> {
+        self.wait = self.wait.saturating_sub(dt);
+        let moved = self.goal.map_or(true, |goal| grid.cell_at(goal) != grid.cell_at(target));
+        if self.wait.is_zero() && (moved || self.path.is_empty()) {
+            self.path = grid.find_path(pos, target, 4000).unwrap_or_default();
+            self.path.reverse();
+            self.goal = Some(target);
+            self.wait = self.replan;
+        }
+        //dropping the waypoints already reached
+        while let Some(&next) = self.path.last() {
+            if (next.x() - pos.x()).abs() <= step && (next.y() - pos.y()).abs() <= step {
+                self.path.pop();
+            } else {
+                break
+            }
+        }
+        let next = *self.path.last()?;
+        let (dx, dy) = (next.x() - pos.x(), next.y() - pos.y());
+        if dx.abs() > step {
+            Some(if dx > 0 {Direction::Right} else {Direction::Left})
+        } else {
+            Some(if dy > 0 {Direction::Down} else {Direction::Up})
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_marks_the_cells_an_obstacle_touches() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 0);
+        grid.block(Rect::new(15, 15, 10, 10));
+        for cell in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            assert!(!grid.is_free(cell), "{:?} should be blocked", cell);
+        }
+        for cell in [(0, 0), (3, 3), (0, 2), (3, 1)] {
+            assert!(grid.is_free(cell), "{:?} should be free", cell);
+        }
+    }
+
+    #[test]
+    fn block_grows_obstacles_by_the_margin() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 10);
+        grid.block(Rect::new(40, 40, 10, 10));
+        assert!(!grid.is_free((3, 3)));
+        assert!(!grid.is_free((5, 5)));
+        assert!(grid.is_free((2, 2)));
+        assert!(grid.is_free((6, 6)));
+    }
+
+    #[test]
+    fn wall_blocks_only_the_edges() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 200, 200), 10, 0);
+        grid.wall(Rect::new(50, 50, 100, 100));
+        for cell in [(5, 5), (14, 5), (5, 14), (14, 14), (10, 5), (5, 10)] {
+            assert!(!grid.is_free(cell), "{:?} should be blocked", cell);
+        }
+        for cell in [(10, 10), (6, 6), (13, 13), (2, 2), (17, 17)] {
+            assert!(grid.is_free(cell), "{:?} should be free", cell);
+        }
+    }
+
+    #[test]
+    fn outside_of_the_grid_is_not_free() {
+        let grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 0);
+        assert!(!grid.is_free((-1, 0)));
+        assert!(!grid.is_free((0, 10)));
+    }
+
+    #[test]
+    fn find_path_in_the_open_ends_at_the_target() {
+        let grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 0);
+        let to = Point::new(85, 85);
+        let path = grid.find_path(Point::new(5, 5), to, 1000).unwrap();
+        assert_eq!(path.last(), Some(&to));
+    }
+
+    #[test]
+    fn find_path_goes_around_obstacles() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 0);
+        //a wall from the top down to y 80, the way is under it
+        grid.block(Rect::new(50, 0, 10, 80));
+        let path = grid.find_path(Point::new(25, 15), Point::new(85, 15), 1000).unwrap();
+        assert!(path.iter().any(|point| point.y() >= 80));
+        for point in &path {
+            assert!(grid.is_free(grid.cell_at(*point)), "{:?} is blocked", point);
+        }
+    }
+
+    #[test]
+    fn find_path_inside_a_room() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 400, 400), 10, 10);
+        let room = Rect::new(50, 50, 300, 300);
+        grid.wall(room);
+        let path = grid.find_path(Point::new(100, 100), Point::new(300, 300), 10000).unwrap();
+        assert_eq!(path.last(), Some(&Point::new(300, 300)));
+        assert!(path.iter().all(|point| room.contains_point(*point)));
+    }
+
+    #[test]
+    fn find_path_doesnt_leave_a_room() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 400, 400), 10, 0);
+        grid.wall(Rect::new(50, 50, 300, 300));
+        assert_eq!(grid.find_path(Point::new(100, 100), Point::new(20, 20), 10000), None);
+    }
+
+    #[test]
+    fn find_path_to_a_blocked_goal_is_none() {
+        let mut grid = NavGrid::new(Rect::new(0, 0, 100, 100), 10, 0);
+        grid.block(Rect::new(80, 80, 10, 10));
+        assert_eq!(grid.find_path(Point::new(5, 5), Point::new(85, 85), 1000), None);
+    }
+
+    #[test]
+    fn find_path_gives_up_after_the_budget() {
+        let grid = NavGrid::new(Rect::new(0, 0, 1000, 1000), 10, 0);
+        assert_eq!(grid.find_path(Point::new(5, 5), Point::new(995, 995), 10), None);
+    }
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+use sdl2::{event::Event, keyboard::{Keycode, Scancode}, pixels::Color, render::WindowCanvas};
+
+use crate::{input::{Action, Controls}, ui::{Container, Flow, Response, Ui, Widget}};
+use super::{Context, Input, Scene, Transition};
+
+#[derive(PartialEq, Copy, Clone)]
+enum Choice {
+    Bind(Action),
+    Reset,
+    Back,
+}
+
+//what the menu calls the actions
+fn label(action: Action) -> &'static str {
+    match action {
+        Action::MoveLeft => "BALRA",
+        Action::MoveRight => "JOBBRA",
+        Action::MoveUp => "FEL",
+        Action::MoveDown => "LE",
+        Action::Sprint => "FUTÁS",
+        Action::Dash => "KITÉRÉS",
+        Action::AttackLeft => "TÁMADÁS BALRA",
+        Action::AttackRight => "TÁMADÁS JOBBRA",
+        Action::AttackUp => "TÁMADÁS FEL",
+        Action::AttackDown => "TÁMADÁS LE",
+        Action::Interact => "HASZNÁLAT",
+        Action::Pause => "SZÜNET",
+    }
+}
+
+//"NAME: KEY" with the first key bound to the action, controller buttons can't be changed here
+fn caption(controls: &Controls, action: Action) -> String {
+    let key = controls.bound(action).iter().find(|name| !name.starts_with("pad:"));
+    format!("{}: {}", label(action), key.map_or("-".to_string(), |key| key.to_uppercase()))
+}
+
+//pressing an action's button and then a key binds the key to it
+pub struct Bindings<'a> {
+    ui: Ui<'a, Choice>,
+    waiting: Option<Action>, //the action the next key is bound to
+    bound: bool, //a key was just bound, it shouldn't press the focused button too
+}
+impl<'a> Bindings<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let fonts = &ctx.fonts;
+        //the moves on the left, the rest on the right
+        let (left, right) = Action::ALL.split_at(Action::ALL.len() / 2);
+        let mut columns = Container::new(Flow::Row).spacing(60);
+        for actions in [left, right] {
+            let mut column = Container::new(Flow::Column).spacing(20);
+            for &action in actions {
+                column = column.with(Widget::button(fonts, &caption(&ctx.controls, action), Choice::Bind(action), (0, 90))?);
+            }
+            columns = columns.with(column);
+        }
+        let buttons = Container::new(Flow::Row)
+            .spacing(40)
+            .with(Widget::button(fonts, "ALAPÉRTELMEZETT", Choice::Reset, (400, 100))?)
+            .with(Widget::button(fonts, "VISSZA", Choice::Back, (400, 100))?);
+        let menu = Container::new(Flow::Column)
+            .spacing(50)
+            .with(Widget::label(fonts, "IRÁNYÍTÁS", 100)?)
+            .with(columns)
+            .with(buttons);
+        Ok(Bindings { ui: Ui::new(menu, ctx)?, waiting: None, bound: false })
+    }
+    //every button shows the key bound to its action again
+    fn relabel(&mut self, ctx: &Context<'a>) -> Result<(), String> {
+        for action in Action::ALL {
+            let content = match self.waiting {
+                Some(waiting) if waiting == action => format!("{}: ...", label(action)),
+                _ => caption(&ctx.controls, action),
+            };
+            if let Some(button) = self.ui.widget(Choice::Bind(action)) {
+                button.set_label(&content)?;
+            }
+        }
+        self.ui.layout(&ctx.display);
+        Ok(())
+    }
+    //the key is taken away from any other action it was bound to, the controller buttons stay
+    fn bind(&mut self, ctx: &mut Context<'a>, action: Action, key: Scancode) -> Result<(), String> {
+        let name = key.name().to_string();
+        if name.is_empty() {
+            return Ok(())
+        }
+        for other in Action::ALL {
+            let names = ctx.controls.bound(other).to_vec();
+            if other != action && names.contains(&name) {
+                ctx.controls.set(other, names.into_iter().filter(|bound| *bound != name).collect())?;
+            }
+        }
+        let pad = ctx.controls.bound(action).iter().filter(|bound| bound.starts_with("pad:")).cloned();
+        let names = [name.clone()].into_iter().chain(pad).collect();
+        ctx.controls.set(action, names)
+    }
+}
+impl<'a> Scene<'a> for Bindings<'a> {
+    fn exit(&mut self, ctx: &mut Context<'a>) -> Result<(), String> {
+        ctx.save_settings();
+        Ok(())
+    }
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.ui.layout(&ctx.display);
+    }
+    //any key can be bound, so they are taken from the events, escape cancels
+    fn event(&mut self, ctx: &mut Context<'a>, event: &Event) -> Result<Transition<'a>, String> {
+        let Event::KeyDown {scancode: Some(code), keycode, repeat: false, .. } = event else {return Ok(Transition::None)};
+        let Some(action) = self.waiting.take() else {return Ok(Transition::None)};
+        if *keycode != Some(Keycode::Escape) {
+            self.bind(ctx, action, *code)?;
+        }
+        self.bound = true;
+        self.relabel(ctx)?;
+        Ok(Transition::None)
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if self.waiting.is_some() || self.bound {
+            self.bound = false;
+            return Ok(Transition::None)
+        }
+        if ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Pop)
+        }
+        match self.ui.update(ctx, input)? {
+            Some(Response::Pressed(Choice::Bind(action))) => {
+                self.waiting = Some(action);
+                self.relabel(ctx)?;
+            }
+            Some(Response::Pressed(Choice::Reset)) => {
+                ctx.controls.reset()?;
+                self.relabel(ctx)?;
+            }
+            Some(Response::Pressed(Choice::Back)) => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::None)
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        self.ui.render(canvas)
+    }
+}
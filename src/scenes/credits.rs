@@ -0,0 +1,45 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::WindowCanvas};
+
+use crate::{display::Display, text::Text, Entity, Presentable};
+use super::{title, Context, Input, Scene, Transition};
+
+pub struct Credits<'a> {
+    logo: Entity<'a>,
+    names: Text<'a>,
+}
+impl<'a> Credits<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let logo = ctx.manifest.entity("logo", 0, 0, &ctx.textures)?;
+        let names = title(ctx, "DR. CAT GAMES", 60)?;
+        let mut credits = Credits { logo, names };
+        credits.layout(&ctx.display);
+        Ok(credits)
+    }
+    //the names under the logo
+    fn layout(&mut self, display: &Display) {
+        self.logo.center(display);
+        self.logo.dst.y -= 100;
+        self.names.center(display);
+        self.names.dst.y += 250;
+    }
+}
+impl<'a> Scene<'a> for Credits<'a> {
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.layout(&ctx.display);
+    }
+    //any key or click goes back, the menu below waits until it's let go
+    fn update(&mut self, ctx: &mut Context<'a>, _input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.any_pressed() {
+            return Ok(Transition::Pop)
+        }
+        Ok(Transition::None)
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        self.logo.present(canvas, 0, 0)?;
+        self.names.present(canvas)
+    }
+}
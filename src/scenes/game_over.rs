@@ -0,0 +1,32 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::WindowCanvas};
+
+use crate::text::Text;
+use super::{title, Context, Input, Scene, Transition};
+
+pub struct GameOver<'a> {
+    text: Text<'a>,
+}
+impl<'a> GameOver<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        Ok(GameOver { text: title(ctx, "JÁTÉK VÉGE", 150)? })
+    }
+}
+impl<'a> Scene<'a> for GameOver<'a> {
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.text.center(&ctx.display);
+    }
+    //any key or click goes back to the main menu below, keys still held from the game don't count
+    fn update(&mut self, ctx: &mut Context<'a>, _input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.any_pressed() {
+            return Ok(Transition::Pop)
+        }
+        Ok(Transition::None)
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        self.text.present(canvas)
+    }
+}
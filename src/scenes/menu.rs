@@ -0,0 +1,50 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::WindowCanvas};
+
+use crate::{input::Action, ui::{Container, Flow, Response, Ui, Widget}};
+use super::{credits::Credits, playing::Playing, Context, Input, Scene, Transition};
+
+#[derive(PartialEq, Copy, Clone)]
+enum Choice {
+    Start,
+    Credits,
+}
+
+pub struct MainMenu<'a> {
+    ui: Ui<'a, Choice>,
+}
+impl<'a> MainMenu<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let fonts = &ctx.fonts;
+        //the buttons under each other in the middle of the screen
+        let buttons = Container::new(Flow::Column)
+            .spacing(50)
+            .with(Widget::button(fonts, "JÁTÉK KEZDÉSE", Choice::Start, (400, 100))?)
+            .with(Widget::button(fonts, "STÁB", Choice::Credits, (200, 100))?);
+        Ok(MainMenu { ui: Ui::new(buttons, ctx)? })
+    }
+}
+impl<'a> Scene<'a> for MainMenu<'a> {
+    fn resume(&mut self, _ctx: &mut Context<'a>) {
+        self.ui.reset();
+    }
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.ui.layout(&ctx.display);
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Quit)
+        }
+        match self.ui.update(ctx, input)? {
+            Some(Response::Pressed(Choice::Start)) => Ok(Transition::Push(Box::new(Playing::new(ctx)?))),
+            Some(Response::Pressed(Choice::Credits)) => Ok(Transition::Push(Box::new(Credits::new(ctx)?))),
+            _ => Ok(Transition::None)
+        }
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        self.ui.render(canvas)
+    }
+}
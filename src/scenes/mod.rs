@@ -0,0 +1,185 @@
+use std::time::Duration;
+use kira::{manager::AudioManager, track::TrackHandle, tween::Tween};
+use sdl2::{event::Event, keyboard::KeyboardState, mouse::MouseState, pixels::Color, rect::Point, render::WindowCanvas};
+
+use crate::{assets::{FontCache, TextureCache}, display::Display, input::Controls, manifest::Manifest, settings::Settings, text::{Align, Text}};
+
+pub mod bindings;
+pub mod credits;
+pub mod game_over;
+pub mod menu;
+pub mod paused;
+pub mod playing;
+pub mod settings;
+pub mod splash;
+
+//everything the scenes share, created once in main
+pub struct Context<'a> {
+    pub textures: TextureCache<'a>,
+    pub manifest: Manifest,
+    pub fonts: FontCache<'a>, //fonts by path and size
+    pub audio: AudioManager,
+    pub music: TrackHandle, //music is played on this track, sound effects on `sfx`
+    pub sfx: TrackHandle,
+    pub alpha: f32, //how far rendering is between the last two ticks, see Clock::alpha
+    pub display: Display, //the logical screen size, things are centered and anchored to it
+    pub controls: Controls, //what the player is doing, updated before every tick
+    pub settings: Settings, //saved when the settings menu is left and when the game quits
+}
+impl<'a> Context<'a> {
+    //setting the tracks' volumes from the settings, every sound goes through the main track
+    pub fn apply_volumes(&mut self) -> Result<(), String> {
+        let master = if self.settings.muted {0.0} else {self.settings.master_volume};
+        self.audio.main_track().set_volume(master, Tween::default()).map_err(|e| e.to_string())?;
+        self.music.set_volume(self.settings.music_volume, Tween::default()).map_err(|e| e.to_string())?;
+        self.sfx.set_volume(self.settings.sfx_volume, Tween::default()).map_err(|e| e.to_string())
+    }
+    //writing the settings file, with the bindings as they are now, the game goes on if it can't be written
+    pub fn save_settings(&mut self) {
+        self.settings.controls = self.controls.names().clone();
+        if let Err(e) = self.settings.save() {
+            eprintln!("the settings couldn't be saved: {}", e);
+        }
+    }
+}
+
+//white text in the menu font, centered on the screen
+pub fn title<'a>(ctx: &Context<'a>, content: &str, size: u16) -> Result<Text<'a>, String> {
+    let mut text = Text::new(&ctx.fonts, "assets/fonts/Aiden-v7DO.otf", size, Color::RGB(255, 255, 255), Point::new(0, 0))?;
+    text.align(Align::Center)?;
+    text.set(content)?;
+    text.center(&ctx.display);
+    Ok(text)
+}
+
+//the state of the keyboard and the mouse in the current frame
+pub struct Input<'k> {
+    pub keyboard: KeyboardState<'k>,
+    pub mouse: MouseState, //buttons and window coordinates
+    pub cursor: Point, //the mouse in logical coordinates
+}
+
+//what the stack should do after a scene handled an event or an update
+pub enum Transition<'a> {
+    None,
+    Push(Box<dyn Scene<'a> + 'a>), //put a scene on top, the current one is paused
+    Pop, //remove the current scene, the one below resumes
+    Switch(Box<dyn Scene<'a> + 'a>), //replace the current scene
+    Reset(Box<dyn Scene<'a> + 'a>), //remove every scene and start over with this one
+    Quit,
+}
+
+pub trait Scene<'a> {
+    //called when the scene gets on the stack
+    fn enter(&mut self, _ctx: &mut Context<'a>) -> Result<(), String> {
+        Ok(())
+    }
+    //called when the scene leaves the stack
+    fn exit(&mut self, _ctx: &mut Context<'a>) -> Result<(), String> {
+        Ok(())
+    }
+    //called when another scene is pushed on top of this one
+    fn pause(&mut self, _ctx: &mut Context<'a>) {}
+    //called when the scene on top of this one is popped
+    fn resume(&mut self, _ctx: &mut Context<'a>) {}
+    //called on every scene when the logical screen size changes, to lay things out again
+    fn resize(&mut self, _ctx: &mut Context<'a>) {}
+    //called for every event of the frame, only on the top scene
+    fn event(&mut self, _ctx: &mut Context<'a>, _event: &Event) -> Result<Transition<'a>, String> {
+        Ok(Transition::None)
+    }
+    //called once per tick, only on the top scene
+    fn update(&mut self, ctx: &mut Context<'a>, input: &Input, dt: Duration) -> Result<Transition<'a>, String>;
+    //called once per frame
+    fn render(&mut self, ctx: &mut Context<'a>, canvas: &mut WindowCanvas, input: &Input) -> Result<(), String>;
+    //overlays are drawn on top of the scene below them instead of replacing it
+    fn overlay(&self) -> bool {
+        false
+    }
+}
+
+pub struct SceneStack<'a> {
+    scenes: Vec<Box<dyn Scene<'a> + 'a>>,
+}
+impl<'a> SceneStack<'a> {
+    pub fn new(mut first: Box<dyn Scene<'a> + 'a>, ctx: &mut Context<'a>) -> Result<Self, String> {
+        first.enter(ctx)?;
+        Ok(SceneStack { scenes: vec![first] })
+    }
+    //the game is over when there are no scenes left
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+    pub fn event(&mut self, ctx: &mut Context<'a>, event: &Event) -> Result<(), String> {
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.event(ctx, event)?,
+            None => return Ok(())
+        };
+        self.apply(ctx, transition)
+    }
+    pub fn update(&mut self, ctx: &mut Context<'a>, input: &Input, dt: Duration) -> Result<(), String> {
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.update(ctx, input, dt)?,
+            None => return Ok(())
+        };
+        self.apply(ctx, transition)
+    }
+    pub fn resize(&mut self, ctx: &mut Context<'a>) {
+        for scene in &mut self.scenes {
+            scene.resize(ctx);
+        }
+    }
+    pub fn render(&mut self, ctx: &mut Context<'a>, canvas: &mut WindowCanvas, input: &Input) -> Result<(), String> {
+        //starting from the topmost scene that isn't an overlay
+        let mut first = self.scenes.len().saturating_sub(1);
+        while first > 0 && self.scenes[first].overlay() {
+            first -= 1;
+        }
+        for scene in &mut self.scenes[first..] {
+            scene.render(ctx, canvas, input)?;
+        }
+        Ok(())
+    }
+    fn apply(&mut self, ctx: &mut Context<'a>, transition: Transition<'a>) -> Result<(), String> {
+        match transition {
+            Transition::None => {}
+            Transition::Push(mut scene) => {
+                if let Some(top) = self.scenes.last_mut() {
+                    top.pause(ctx);
+                }
+                scene.enter(ctx)?;
+                self.scenes.push(scene);
+            }
+            Transition::Pop => {
+                if let Some(mut top) = self.scenes.pop() {
+                    top.exit(ctx)?;
+                }
+                if let Some(top) = self.scenes.last_mut() {
+                    top.resume(ctx);
+                }
+            }
+            Transition::Switch(mut scene) => {
+                if let Some(mut top) = self.scenes.pop() {
+                    top.exit(ctx)?;
+                }
+                scene.enter(ctx)?;
+                self.scenes.push(scene);
+            }
+            Transition::Reset(mut scene) => {
+                self.clear(ctx)?;
+                scene.enter(ctx)?;
+                self.scenes.push(scene);
+            }
+            Transition::Quit => {
+                self.clear(ctx)?;
+            }
+        }
+        Ok(())
+    }
+    fn clear(&mut self, ctx: &mut Context<'a>) -> Result<(), String> {
+        while let Some(mut top) = self.scenes.pop() {
+            top.exit(ctx)?;
+        }
+        Ok(())
+    }
+}
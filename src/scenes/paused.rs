@@ -0,0 +1,63 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::{BlendMode, WindowCanvas}};
+
+use crate::{input::Action, ui::{Container, Flow, Response, Ui, Widget}};
+use super::{menu::MainMenu, settings::SettingsMenu, Context, Input, Scene, Transition};
+
+#[derive(PartialEq, Copy, Clone)]
+enum Choice {
+    Resume,
+    Settings,
+    Quit,
+}
+
+//dims the game below, which isn't updated while this is on top
+pub struct Paused<'a> {
+    ui: Ui<'a, Choice>,
+}
+impl<'a> Paused<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let fonts = &ctx.fonts;
+        //the title above the buttons, the buttons under each other in the middle of the screen
+        let buttons = Container::new(Flow::Column)
+            .spacing(50)
+            .with(Widget::button(fonts, "FOLYTATÁS", Choice::Resume, (400, 100))?)
+            .with(Widget::button(fonts, "BEÁLLÍTÁSOK", Choice::Settings, (400, 100))?)
+            .with(Widget::button(fonts, "VISSZA A MENÜBE", Choice::Quit, (400, 100))?);
+        let menu = Container::new(Flow::Column)
+            .spacing(80)
+            .with(Widget::label(fonts, "SZÜNET", 150)?)
+            .with(buttons);
+        Ok(Paused { ui: Ui::new(menu, ctx)? })
+    }
+}
+impl<'a> Scene<'a> for Paused<'a> {
+    fn resume(&mut self, _ctx: &mut Context<'a>) {
+        self.ui.reset();
+    }
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.ui.layout(&ctx.display);
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Pop)
+        }
+        match self.ui.update(ctx, input)? {
+            Some(Response::Pressed(Choice::Resume)) => Ok(Transition::Pop),
+            Some(Response::Pressed(Choice::Settings)) => Ok(Transition::Push(Box::new(SettingsMenu::new(ctx)?))),
+            Some(Response::Pressed(Choice::Quit)) => Ok(Transition::Reset(Box::new(MainMenu::new(ctx)?))),
+            _ => Ok(Transition::None)
+        }
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, 160));
+        canvas.fill_rect(None)?;
+        canvas.set_blend_mode(BlendMode::None);
+
+        self.ui.render(canvas)
+    }
+    fn overlay(&self) -> bool {
+        true
+    }
+}
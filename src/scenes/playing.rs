@@ -0,0 +1,333 @@
+use std::{collections::HashMap, rc::Rc, time::Duration};
+use kira::sound::static_sound::{StaticSoundData, StaticSoundSettings};
+use sdl2::{pixels::Color, rect::Point, render::WindowCanvas};
+
+use crate::{
+    dialogue::{Conversation, DialogueBox, Flags, Script}, health::Health, hud::{Counter, Hearts}, input::Action, inventory::Inventory, tilemap::TileMap,
+    Anim, Animal, Audio, Character, Direction, Entity, Facing, Game, Interactable, Interaction, Presentable, World,
+    SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+use super::{game_over::GameOver, paused::Paused, Context, Input, Scene, Transition};
+
+//holding an attack key attacks again this often
+const ATTACK_REPEAT: Duration = Duration::from_millis(400);
+
+//entities are placed relative to the middle of the screen, map objects relative to the map's corner
+fn from_map(point: Point) -> (i32, i32) {
+    (point.x() - (SCREEN_WIDTH / 2) as i32, point.y() - (SCREEN_HEIGHT / 2) as i32)
+}
+
+//what happens when the character uses an interactable zone of the map, by the zone's name
+fn interaction<'a>(name: &str) -> Option<Interaction<'a>> {
+    match name {
+        //the key the cat lost, it can only be found once
+        "test" => Some(Rc::new(|game: &mut Game<'_, 'a>| {
+            if game.flags.insert("found_key".to_string()) {
+                game.inventory.add("key", 1);
+                game.world.camera.shake(2.0, Duration::from_millis(100));
+            }
+            Ok(Transition::None)
+        })),
+        "well" => Some(Rc::new(|game: &mut Game<'_, 'a>| {
+            game.char.health.heal(1);
+            Ok(Transition::None)
+        })),
+        _ => None
+    }
+}
+//an interaction starting a conversation
+fn talk<'a>(script: Rc<Script>) -> Interaction<'a> {
+    Rc::new(move |game: &mut Game<'_, 'a>| {
+        game.talk(&script);
+        Ok(Transition::None)
+    })
+}
+
+pub struct Playing<'a> {
+    world: World<'a>,
+    sponge: Character<'a>,
+    inventory: Inventory,
+    flags: Flags, //what happened so far, set and checked by conversations
+    scripts: HashMap<String, Rc<Script>>, //the conversations of the level by path, see the "dialogue" property of map objects
+    dialogue: Option<Conversation>, //the conversation going on, the world waits while there is one
+    dialogue_box: DialogueBox<'a>,
+    hearts: Hearts<'a>, //the character's health on the HUD
+    keys: Counter<'a>, //the keys in the inventory on the HUD
+    dash: Entity<'a>, //the dash cooldown indicator
+    running: Audio,
+    slash: Audio,
+    dying: bool, //the death animation is playing, the game ends when it's over
+    i: u8, //background color cycle
+}
+impl<'a> Playing<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let manifest = &ctx.manifest;
+        let textures = &ctx.textures;
+
+        //defining character
+        let sponge = manifest.entity("sponge", 0, 0, textures)?;
+        //failing early instead of mid-game if the manifest misses an animation the character needs
+        for dir in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
+            sponge.slot(Anim::Walk(dir))?;
+            sponge.slot(Anim::Dash(dir))?;
+        }
+        //attacking up and down is optional, there is no art for it yet
+        sponge.slot(Anim::Attack(Direction::Left))?;
+        sponge.slot(Anim::Attack(Direction::Right))?;
+        sponge.slot(Anim::Death)?;
+
+        //an entity at 0, 0 is in the middle of the screen, the spawn point moves it later
+        let mut sponge = Character {
+            x: (SCREEN_WIDTH / 2) as f32,
+            y: (SCREEN_HEIGHT / 2) as f32,
+            prev_x: (SCREEN_WIDTH / 2) as f32,
+            prev_y: (SCREEN_HEIGHT / 2) as f32,
+            rep: sponge,
+            dir: Direction::Right,
+            facing: Facing::Right,
+            velocity: (0.0, 0.0),
+            speed: 60,
+            step: 0,
+            dodge_cooldown: Duration::ZERO,
+            health: Health::new(3, Duration::from_secs(1)),
+        };
+
+        //defining hearts
+        let heart = manifest.entity("heart", -((SCREEN_WIDTH / 2) as i32) + 25, -((SCREEN_HEIGHT / 2) as i32) + 25, textures)?;
+        let hearts = Hearts::new(heart, &sponge.health);
+
+        //dash cooldown
+        let dash = manifest.entity("dash", (SCREEN_WIDTH as i32 / 2)-44, -(SCREEN_HEIGHT as i32 / 2)+28, textures)?;
+
+        //the level, everything in it comes from the map's object layers
+        let mut tiles = TileMap::load("assets/maps/world.tmj", textures)?;
+        let map = tiles.hitboxes();
+        let objects = std::mem::take(&mut tiles.objects);
+        //every conversation is read and checked up front, so a broken file is found when the level loads
+        let mut scripts = HashMap::new();
+        for path in objects.iter().filter_map(|object| object.text("dialogue")) {
+            if !scripts.contains_key(path) {
+                scripts.insert(path.to_string(), Rc::new(Script::load(path)?));
+            }
+        }
+        //static entities are baked into the navigation grid, so they go into the world first
+        let mut statics = vec![];
+        for object in objects.iter().filter(|object| object.kind == "entity") {
+            let (x, y) = from_map(object.center());
+            statics.push(manifest.entity(&object.name, x, y, textures)?);
+        }
+        let mut world = World::from(map, tiles, statics, textures)?;
+        world.camera.resize(ctx.display.width, ctx.display.height);
+
+        let mut spawned = false;
+        for object in &objects {
+            let (x, y) = from_map(object.center());
+            match object.kind.as_str() {
+                "spawn" if object.name == "player" => {
+                    sponge.place(object.center());
+                    world.camera.snap(object.center());
+                    spawned = true;
+                }
+                "animal" => {
+                    let speed = object.number("speed").unwrap_or(180.0) as i32;
+                    let mut animal = Animal::from(speed, manifest.entity(&object.name, x, y, textures)?);
+                    animal.dialogue = object.text("dialogue").map(String::from);
+                    world.add(animal);
+                }
+                "enemy" => world.add(manifest.enemy(&object.name, x, y, textures)?),
+                "interactable" => {
+                    //zones with a "dialogue" property start that conversation, the rest are looked up by name
+                    let callback = match object.text("dialogue") {
+                        Some(path) => Some(talk(scripts[path].clone())),
+                        None => interaction(&object.name),
+                    };
+                    world.add_interaction(Interactable::new(object.area, callback, world.map.area));
+                }
+                _ => {}
+            }
+        }
+        if !spawned {
+            return Err("the map has no player spawn point".to_string());
+        }
+
+        //sound
+        let sound_data = StaticSoundData::from_file("assets/sounds/running_in_grass.mp3", StaticSoundSettings::new().track(&ctx.sfx)).map_err(|e| e.to_string())?;
+        let sound = ctx.audio.play(sound_data.clone()).map_err(|e| e.to_string())?;
+        let running = Audio {data: sound_data, current: sound};
+
+        let sound_data = StaticSoundData::from_file("assets/sounds/slash.mp3", StaticSoundSettings::new().track(&ctx.sfx)).map_err(|e| e.to_string())?;
+        let sound = ctx.audio.play(sound_data.clone()).map_err(|e| e.to_string())?;
+        let slash = Audio {data: sound_data, current: sound};
+
+        Ok(Playing {
+            world,
+            sponge,
+            inventory: Inventory::default(),
+            flags: Flags::new(),
+            scripts,
+            dialogue: None,
+            dialogue_box: DialogueBox::new(&ctx.fonts)?,
+            hearts,
+            keys: Counter::new(&ctx.fonts, "KULCS")?,
+            dash,
+            running,
+            slash,
+            dying: false,
+            i: 0,
+        })
+    }
+}
+impl<'a> Scene<'a> for Playing<'a> {
+    fn exit(&mut self, _ctx: &mut Context<'a>) -> Result<(), String> {
+        self.running.stop(0);
+        self.slash.stop(0);
+        Ok(())
+    }
+    //freezing the sounds along with the world while a menu is on top
+    fn pause(&mut self, _ctx: &mut Context<'a>) {
+        //no more ticks until resumed, so the world is drawn where it is instead of interpolated
+        self.world.camera.snapshot();
+        self.sponge.snapshot();
+        self.running.pause();
+        self.slash.pause();
+    }
+    fn resume(&mut self, _ctx: &mut Context<'a>) {
+        self.running.resume();
+        self.slash.resume();
+    }
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.world.camera.resize(ctx.display.width, ctx.display.height);
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, _input: &Input, dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Push(Box::new(Paused::new(ctx)?)))
+        }
+        //the world waits while talking
+        if let Some(conversation) = &mut self.dialogue {
+            self.world.camera.snapshot();
+            self.sponge.snapshot();
+            self.sponge.rep.switch_to(Anim::Idle)?;
+            self.running.stop(0);
+            if !conversation.update(&ctx.controls, &mut self.flags, &mut self.inventory, dt) {
+                self.dialogue = None;
+            }
+            return Ok(Transition::None)
+        }
+        let controls = &ctx.controls;
+        let sponge = &mut self.sponge;
+        self.world.camera.snapshot();
+        sponge.snapshot();
+        let hp = sponge.health.hp;
+
+        //no attacking while dying
+        if !self.dying {
+            if controls.repeated(Action::AttackRight, ATTACK_REPEAT, ATTACK_REPEAT) {
+                self.slash.play(&mut ctx.audio);
+                sponge.rep.play(Some(Anim::Attack(Direction::Right)))?;
+            }
+            else if controls.repeated(Action::AttackLeft, ATTACK_REPEAT, ATTACK_REPEAT) {
+                self.slash.play(&mut ctx.audio);
+                sponge.rep.play(Some(Anim::Attack(Direction::Left)))?;
+            }
+            else if sponge.rep.has(Anim::Attack(Direction::Up)) && controls.repeated(Action::AttackUp, ATTACK_REPEAT, ATTACK_REPEAT) {
+                self.slash.play(&mut ctx.audio);
+                sponge.rep.play(Some(Anim::Attack(Direction::Up)))?;
+            }
+            else if sponge.rep.has(Anim::Attack(Direction::Down)) && controls.repeated(Action::AttackDown, ATTACK_REPEAT, ATTACK_REPEAT) {
+                self.slash.play(&mut ctx.audio);
+                sponge.rep.play(Some(Anim::Attack(Direction::Down)))?;
+            }
+        }
+
+        //using an interactable, or talking to someone next to the character
+        let interaction = self.world.check_interact(sponge, controls);
+        let conversation = if interaction.is_none() {self.world.check_talk(sponge, controls)} else {None};
+        let mut game = Game {
+            world: &mut self.world,
+            char: sponge,
+            inventory: &mut self.inventory,
+            flags: &mut self.flags,
+            dialogue: &mut self.dialogue,
+        };
+        if let Some(script) = conversation.and_then(|path| self.scripts.get(&path)) {
+            game.talk(script);
+        }
+        if let Some(interaction) = interaction {
+            let transition = interaction(&mut game)?;
+            if !matches!(transition, Transition::None) {
+                return Ok(transition)
+            }
+        }
+
+        let mut moved = false;
+        if sponge.rep.current().movable {
+            //checking for sprint
+            sponge.speed(controls, &mut self.dash, dt)?;
+
+            //movement
+            moved = sponge.movement(controls, &self.world, dt)?;
+        }
+        //if not moved then stand
+        if !moved {
+            sponge.rep.switch_to(Anim::Idle)?;
+            self.running.stop(0);
+        } else {
+            self.running.play(&mut ctx.audio);
+        }
+
+        if sponge.health.is_dead() && !self.dying {
+            sponge.rep.force_play(Anim::Death)?;
+            self.running.stop(0);
+            //closing in on the character while it dies
+            self.world.camera.zoom_to(1.5);
+            self.dying = true;
+        }
+
+        //play next frame of animations
+        sponge.rep.next(dt);
+        sponge.health.next(dt);
+        self.hearts.sync(&sponge.health)?;
+        self.hearts.next(dt);
+        self.keys.sync(self.inventory.count("key"))?;
+        self.dash.next(dt);
+
+        //hitting whatever is in front of the character
+        if let Some((area, dir)) = sponge.attack_hitbox() {
+            let (x, y) = dir.vector();
+            if self.world.hit(area, 1, (x * 30, y * 30)) > 0 {
+                self.world.camera.shake(4.0, Duration::from_millis(100));
+            }
+        }
+        self.world.do_behaviours(sponge, dt);
+        if sponge.health.hp < hp {
+            self.world.camera.shake(12.0, Duration::from_millis(250));
+        }
+        self.world.camera.follow(sponge.pixel(), dt);
+
+        self.i = (self.i + 1) % 255;
+
+        if self.dying && !sponge.rep.is_playing(Anim::Death) {
+            return Ok(Transition::Switch(Box::new(GameOver::new(ctx)?)))
+        }
+        Ok(Transition::None)
+    }
+    fn render(&mut self, ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(self.i, 64, 255 - self.i));
+        canvas.clear();
+
+        self.world.camera.interpolate(ctx.alpha);
+        self.world.present(canvas, &self.sponge)?;
+
+        self.world.present_interactions(canvas, &self.sponge)?;
+
+        self.hearts.present(canvas)?;
+        //the dash indicator sticks to the right edge however wide the screen is
+        self.dash.present(canvas, ctx.display.width as i32 - SCREEN_WIDTH as i32, 0)?;
+        self.keys.present(canvas, &ctx.display)?;
+
+        if let Some(conversation) = &self.dialogue {
+            self.dialogue_box.present(canvas, conversation, &ctx.display, &ctx.textures)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::WindowCanvas};
+
+use crate::{
+    display::WindowMode,
+    input::Action,
+    settings::{FRAME_CAPS, RESOLUTIONS},
+    ui::{Container, Flow, Node, Response, Ui, Widget},
+};
+use super::{bindings::Bindings, Context, Input, Scene, Transition};
+
+//how much a volume changes per key press
+const VOLUME_STEP: f64 = 0.05;
+const LABEL_SIZE: u16 = 50;
+
+#[derive(PartialEq, Copy, Clone)]
+enum Choice {
+    Mode,
+    Resolution,
+    FrameCap,
+    Vsync,
+    Master,
+    Music,
+    Sfx,
+    Mute,
+    Controls,
+    Back,
+}
+
+//a label with the widget it names next to it
+fn row<'a>(ctx: &Context<'a>, label: &str, widget: impl Into<Node<'a, Choice>>) -> Result<Container<'a, Choice>, String> {
+    Ok(Container::new(Flow::Row)
+        .spacing(40)
+        .with(Widget::label(&ctx.fonts, label, LABEL_SIZE)?)
+        .with(widget))
+}
+
+//changes are applied right away, and saved when the menu is left
+pub struct SettingsMenu<'a> {
+    ui: Ui<'a, Choice>,
+    resolutions: Vec<(u32, u32)>, //the options of the resolution dropdown, a size set in the file is kept as one too
+}
+impl<'a> SettingsMenu<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let fonts = &ctx.fonts;
+        let settings = &ctx.settings;
+        let mode = WindowMode::ALL.iter().position(|mode| *mode == settings.window_mode).unwrap_or(0);
+        let mut resolutions = RESOLUTIONS.to_vec();
+        if !resolutions.contains(&settings.resolution) {
+            resolutions.push(settings.resolution);
+        }
+        let resolution = resolutions.iter().position(|resolution| *resolution == settings.resolution).unwrap_or(0);
+        let resolution_names: Vec<String> = resolutions.iter().map(|(w, h)| format!("{}x{}", w, h)).collect();
+        let resolution_options: Vec<&str> = resolution_names.iter().map(String::as_str).collect();
+        let cap_names: Vec<String> = FRAME_CAPS.iter().map(|cap| if *cap == 0 {"NINCS".to_string()} else {cap.to_string()}).collect();
+        let caps: Vec<&str> = cap_names.iter().map(String::as_str).collect();
+        let cap = FRAME_CAPS.iter().position(|cap| *cap == settings.frame_cap).unwrap_or(0);
+        let mut master = Widget::slider(fonts, Choice::Master, settings.master_volume, VOLUME_STEP, 500)?;
+        master.enabled = !settings.muted;
+
+        //the screen at the top so the dropdowns have room to open, then the sound, the buttons at the bottom
+        let buttons = Container::new(Flow::Row)
+            .spacing(40)
+            .with(Widget::button(fonts, "IRÁNYÍTÁS", Choice::Controls, (400, 100))?)
+            .with(Widget::button(fonts, "VISSZA", Choice::Back, (400, 100))?);
+        let menu = Container::new(Flow::Column)
+            .anchor((0.5, 0.0), (0, 0))
+            .padding(60)
+            .spacing(25)
+            .with(row(ctx, "MEGJELENÍTÉS", Widget::dropdown(fonts, &["ABLAK", "KERET NÉLKÜL", "TELJES"], Choice::Mode, mode)?)?)
+            .with(row(ctx, "FELBONTÁS", Widget::dropdown(fonts, &resolution_options, Choice::Resolution, resolution)?)?)
+            .with(row(ctx, "FPS KORLÁT", Widget::dropdown(fonts, &caps, Choice::FrameCap, cap)?)?)
+            .with(Widget::checkbox(fonts, "VSYNC (ÚJRAINDÍTÁS UTÁN)", Choice::Vsync, settings.vsync)?)
+            .with(row(ctx, "ÖSSZES", master)?)
+            .with(row(ctx, "ZENE", Widget::slider(fonts, Choice::Music, settings.music_volume, VOLUME_STEP, 500)?)?)
+            .with(row(ctx, "EFFEKTEK", Widget::slider(fonts, Choice::Sfx, settings.sfx_volume, VOLUME_STEP, 500)?)?)
+            .with(Widget::checkbox(fonts, "NÉMÍTÁS", Choice::Mute, settings.muted)?)
+            .with(buttons);
+        Ok(SettingsMenu { ui: Ui::new(menu, ctx)?, resolutions })
+    }
+}
+impl<'a> Scene<'a> for SettingsMenu<'a> {
+    fn exit(&mut self, ctx: &mut Context<'a>) -> Result<(), String> {
+        ctx.save_settings();
+        Ok(())
+    }
+    fn resume(&mut self, _ctx: &mut Context<'a>) {
+        self.ui.reset();
+    }
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.ui.layout(&ctx.display);
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        //the pause key closes an open dropdown first
+        if !self.ui.open() && ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Pop)
+        }
+        match self.ui.update(ctx, input)? {
+            Some(Response::Selected(Choice::Mode, i)) => {
+                ctx.settings.window_mode = WindowMode::ALL[i];
+                ctx.display.outdated = true;
+            }
+            Some(Response::Selected(Choice::Resolution, i)) => {
+                ctx.settings.resolution = self.resolutions[i];
+                ctx.display.outdated = true;
+            }
+            Some(Response::Selected(Choice::FrameCap, i)) => ctx.settings.frame_cap = FRAME_CAPS[i],
+            Some(Response::Toggled(Choice::Vsync, vsync)) => ctx.settings.vsync = vsync,
+            Some(Response::Changed(Choice::Master, volume)) => {
+                ctx.settings.master_volume = volume;
+                ctx.apply_volumes()?;
+            }
+            Some(Response::Changed(Choice::Music, volume)) => {
+                ctx.settings.music_volume = volume;
+                ctx.apply_volumes()?;
+            }
+            Some(Response::Changed(Choice::Sfx, volume)) => {
+                ctx.settings.sfx_volume = volume;
+                ctx.apply_volumes()?;
+            }
+            //the master volume can't be changed while muted
+            Some(Response::Toggled(Choice::Mute, muted)) => {
+                ctx.settings.muted = muted;
+                if let Some(master) = self.ui.widget(Choice::Master) {
+                    master.enabled = !muted;
+                }
+                ctx.apply_volumes()?;
+            }
+            Some(Response::Pressed(Choice::Controls)) => return Ok(Transition::Push(Box::new(Bindings::new(ctx)?))),
+            Some(Response::Pressed(Choice::Back)) => return Ok(Transition::Pop),
+            _ => {}
+        }
+        Ok(Transition::None)
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.clear();
+
+        self.ui.render(canvas)
+    }
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+use sdl2::{pixels::Color, render::WindowCanvas};
+
+use crate::{input::Action, Entity, Presentable};
+use super::{menu::MainMenu, Context, Input, Scene, Transition};
+
+//the logo on a background fading from white to black
+pub struct Splash<'a> {
+    logo: Entity<'a>,
+    fade: u8,
+}
+impl<'a> Splash<'a> {
+    pub fn new(ctx: &mut Context<'a>) -> Result<Self, String> {
+        let mut logo = ctx.manifest.entity("logo", 0, 0, &ctx.textures)?;
+        logo.center(&ctx.display);
+        Ok(Splash { logo, fade: 0 })
+    }
+}
+impl<'a> Scene<'a> for Splash<'a> {
+    fn resize(&mut self, ctx: &mut Context<'a>) {
+        self.logo.center(&ctx.display);
+    }
+    fn update(&mut self, ctx: &mut Context<'a>, _input: &Input, _dt: Duration) -> Result<Transition<'a>, String> {
+        if ctx.controls.pressed(Action::Pause) {
+            return Ok(Transition::Quit)
+        }
+        if self.fade == 255 {
+            return Ok(Transition::Switch(Box::new(MainMenu::new(ctx)?)))
+        }
+        self.fade += 1;
+        Ok(Transition::None)
+    }
+    fn render(&mut self, _ctx: &mut Context<'a>, canvas: &mut WindowCanvas, _input: &Input) -> Result<(), String> {
+        canvas.set_draw_color(Color::RGB(255-self.fade, 255-self.fade, 255-self.fade));
+        canvas.clear();
+
+        self.logo.present(canvas, 0, 0)
+    }
+}
@@ -0,0 +1,65 @@
+use std::{collections::BTreeMap, fs};
+use serde::{Deserialize, Serialize};
+
+use crate::{display::WindowMode, input::Controls, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+//the window sizes the settings menu offers
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+//the frame caps the settings menu offers, 0 is no cap
+pub const FRAME_CAPS: [u32; 5] = [30, 60, 120, 144, 0];
+
+//everything the player can change, kept in a file in the user's config directory
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(skip)]
+    path: String,
+    pub master_volume: f64, //every sound (0.0 - 1.0)
+    pub music_volume: f64,
+    pub sfx_volume: f64,
+    pub muted: bool, //nothing can be heard, the volumes are kept for when it's turned back on
+    pub window_mode: WindowMode,
+    pub resolution: (u32, u32), //the window's size when windowed, the monitor's resolution in fullscreen
+    pub vsync: bool, //only applied when the game starts
+    pub frame_cap: u32, //frames per second at most, 0 leaves it to vsync
+    pub controls: BTreeMap<String, Vec<String>>, //action -> key and controller button names, see Controls
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            path: String::new(),
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            muted: false,
+            window_mode: WindowMode::Windowed,
+            resolution: (SCREEN_WIDTH, SCREEN_HEIGHT),
+            vsync: false,
+            frame_cap: 60,
+            controls: Controls::default_names(),
+        }
+    }
+}
+impl Settings {
+    //reading the settings from `path`, anything missing from the file is the default
+    //a file that can't be read is left alone and the defaults are used, saving replaces it
+    pub fn load(path: &str) -> Self {
+        let mut settings = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
+                eprintln!("{}: {}, using the default settings", path, e);
+                Settings::default()
+            }),
+            Err(_) => Settings::default(),
+        };
+        settings.path = path.to_string();
+        settings.master_volume = settings.master_volume.clamp(0.0, 1.0);
+        settings.music_volume = settings.music_volume.clamp(0.0, 1.0);
+        settings.sfx_volume = settings.sfx_volume.clamp(0.0, 1.0);
+        settings.resolution = (settings.resolution.0.max(640), settings.resolution.1.max(360));
+        settings
+    }
+    pub fn save(&self) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(&self.path, content).map_err(|e| format!("{}: {}", self.path, e))
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::HashMap, hash::Hash};
+use sdl2::rect::Rect;
+
+type Cell = (i32, i32); //(col, row) on the grid
+
+//the cells from (left, top) to (right, bottom) a rect touches
+fn cells((left, top): Cell, (right, bottom): Cell) -> impl Iterator<Item = Cell> {
+    (top..=bottom).flat_map(move |row| (left..=right).map(move |col| (col, row)))
+}
+
+//a uniform grid over the world, so finding what is near an area doesn't look at everything in the world
+pub struct SpatialGrid<K> {
+    cell: i32, //the size of one cell in pixels
+    cells: HashMap<Cell, Vec<K>>, //the keys touching each cell, empty cells are left out
+    rects: HashMap<K, Rect>, //the rect of every key
+}
+impl<K: Copy + Eq + Hash + Ord> SpatialGrid<K> {
+    pub fn new(cell: u32) -> Self {
+        SpatialGrid { cell: cell as i32, cells: HashMap::new(), rects: HashMap::new() }
+    }
+    fn span(&self, rect: Rect) -> (Cell, Cell) {
+        let at = |value: i32| value.div_euclid(self.cell);
+        ((at(rect.left()), at(rect.top())), (at(rect.right() - 1), at(rect.bottom() - 1)))
+    }
+    pub fn insert(&mut self, key: K, rect: Rect) {
+        self.remove(key);
+        let (from, to) = self.span(rect);
+        for cell in cells(from, to) {
+            self.cells.entry(cell).or_default().push(key);
+        }
+        self.rects.insert(key, rect);
+    }
+    pub fn remove(&mut self, key: K) {
+        let Some(rect) = self.rects.remove(&key) else {return};
+        let (from, to) = self.span(rect);
+        for cell in cells(from, to) {
+            if let Some(keys) = self.cells.get_mut(&cell) {
+                keys.retain(|other| *other != key);
+                if keys.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+    //following a key that moved, the cells are only touched if it got into other ones
+    pub fn update(&mut self, key: K, rect: Rect) {
+        let same_cells = self.rects.get(&key).is_some_and(|old| self.span(*old) == self.span(rect));
+        if same_cells {
+            self.rects.insert(key, rect);
+        } else {
+            self.insert(key, rect);
+        }
+    }
+    pub fn retain(&mut self, keep: impl Fn(K) -> bool) {
+        let gone: Vec<K> = self.rects.keys().copied().filter(|key| !keep(*key)).collect();
+        for key in gone {
+            self.remove(key);
+        }
+    }
+    //every key whose rect overlaps `area` with its rect, ordered by key
+    pub fn query(&self, area: Rect) -> Vec<(K, Rect)> {
+        let (from, to) = self.span(area);
+        let mut keys: Vec<K> = cells(from, to)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .map(|key| (key, self.rects[&key]))
+            .filter(|(_, rect)| rect.has_intersection(area))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(found: Vec<(u32, Rect)>) -> Vec<u32> {
+        found.into_iter().map(|(key, _)| key).collect()
+    }
+
+    #[test]
+    fn query_finds_overlapping_rects_in_key_order() {
+        let mut grid = SpatialGrid::new(64);
+        grid.insert(2, Rect::new(10, 10, 20, 20));
+        grid.insert(1, Rect::new(40, 10, 100, 20));
+        grid.insert(3, Rect::new(500, 500, 10, 10));
+        assert_eq!(keys(grid.query(Rect::new(0, 0, 64, 64))), vec![1, 2]);
+        assert_eq!(keys(grid.query(Rect::new(495, 495, 10, 10))), vec![3]);
+    }
+
+    #[test]
+    fn query_skips_rects_only_sharing_a_cell() {
+        let mut grid = SpatialGrid::new(64);
+        grid.insert(1, Rect::new(0, 0, 10, 10));
+        assert!(grid.query(Rect::new(20, 20, 10, 10)).is_empty());
+    }
+
+    #[test]
+    fn negative_coordinates_have_their_own_cells() {
+        let mut grid = SpatialGrid::new(64);
+        grid.insert(1, Rect::new(-30, -30, 20, 20));
+        assert_eq!(keys(grid.query(Rect::new(-20, -20, 5, 5))), vec![1]);
+        assert!(grid.query(Rect::new(5, 5, 5, 5)).is_empty());
+    }
+
+    #[test]
+    fn update_moves_a_key() {
+        let mut grid = SpatialGrid::new(64);
+        grid.insert(1, Rect::new(0, 0, 10, 10));
+        grid.update(1, Rect::new(300, 300, 10, 10));
+        assert!(grid.query(Rect::new(0, 0, 10, 10)).is_empty());
+        assert_eq!(grid.query(Rect::new(300, 300, 10, 10)), vec![(1, Rect::new(300, 300, 10, 10))]);
+        //within the same cells only the rect changes
+        grid.update(1, Rect::new(305, 305, 10, 10));
+        assert_eq!(grid.query(Rect::new(300, 300, 10, 10)), vec![(1, Rect::new(305, 305, 10, 10))]);
+    }
+
+    #[test]
+    fn inserting_again_replaces_the_rect() {
+        let mut grid = SpatialGrid::new(64);
+        grid.insert(1, Rect::new(0, 0, 10, 10));
+        grid.insert(1, Rect::new(200, 0, 10, 10));
+        assert!(grid.query(Rect::new(0, 0, 10, 10)).is_empty());
+        assert_eq!(keys(grid.query(Rect::new(0, 0, 300, 300))), vec![1]);
+    }
+
+    #[test]
+    fn remove_and_retain_forget_keys() {
+        let mut grid = SpatialGrid::new(64);
+        for key in 0..6 {
+            grid.insert(key, Rect::new(key as i32 * 50, 0, 40, 40));
+        }
+        grid.remove(0);
+        grid.remove(10);
+        grid.retain(|key| key % 2 == 1);
+        assert_eq!(keys(grid.query(Rect::new(0, 0, 400, 40))), vec![1, 3, 5]);
+        assert!(grid.cells.values().all(|keys| keys.iter().all(|key| key % 2 == 1)));
+    }
+}
@@ -0,0 +1,234 @@
+use std::{ops::Range, rc::Rc};
+use sdl2::{
+    pixels::{Color, PixelFormatEnum},
+    rect::{Point, Rect},
+    render::{Texture, TextureCreator, WindowCanvas},
+    surface::Surface,
+    ttf::Font,
+    video::WindowContext,
+};
+
+use crate::{assets::FontCache, display::Display};
+
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+//text that can change, it is only rendered again when something about it did
+pub struct Text<'a> {
+    font: Rc<Font<'a, 'static>>,
+    loader: &'a TextureCreator<WindowContext>,
+    content: String,
+    color: Color,
+    spans: Vec<(Range<usize>, Color)>, //other colors for some of the characters, by their index, later spans win
+    width: Option<u32>, //the rows are wrapped to this width and aligned within it
+    align: Align, //the rows are aligned to each other, and re-rendering keeps this edge of `dst` in place
+    outline: Option<(Rc<Font<'a, 'static>>, u16, Color)>, //the outline font, its thickness and color
+    shadow: Option<(u32, Color)>, //a copy of the text this many pixels to the bottom right
+    visible: Option<usize>, //the characters from this index on take up room but aren't drawn
+    current: Option<Texture<'a>>, //None when there is nothing to draw
+    pub dst: Rect,
+}
+impl<'a> Text<'a> {
+    pub fn new(fonts: &FontCache<'a>, path: &str, size: u16, color: Color, point: Point) -> Result<Self, String> {
+        Ok(Text {
+            font: fonts.load(path, size)?,
+            loader: fonts.loader(),
+            content: String::new(),
+            color,
+            spans: vec![],
+            width: None,
+            align: Align::Left,
+            outline: None,
+            shadow: None,
+            visible: None,
+            current: None,
+            dst: Rect::new(point.x(), point.y(), 1, 1),
+        })
+    }
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+    pub fn set(&mut self, content: &str) -> Result<(), String> {
+        if self.content == content {
+            return Ok(())
+        }
+        self.content = content.to_string();
+        self.render()
+    }
+    pub fn set_color(&mut self, color: Color) -> Result<(), String> {
+        if self.color == color {
+            return Ok(())
+        }
+        self.color = color;
+        self.render()
+    }
+    pub fn set_spans(&mut self, spans: Vec<(Range<usize>, Color)>) -> Result<(), String> {
+        if self.spans == spans {
+            return Ok(())
+        }
+        self.spans = spans;
+        self.render()
+    }
+    pub fn set_visible(&mut self, visible: Option<usize>) -> Result<(), String> {
+        if self.visible == visible {
+            return Ok(())
+        }
+        self.visible = visible;
+        self.render()
+    }
+    pub fn wrap(&mut self, width: Option<u32>) -> Result<(), String> {
+        if self.width == width {
+            return Ok(())
+        }
+        self.width = width;
+        self.render()
+    }
+    pub fn align(&mut self, align: Align) -> Result<(), String> {
+        if self.align == align {
+            return Ok(())
+        }
+        self.align = align;
+        self.render()
+    }
+    pub fn outline(&mut self, fonts: &FontCache<'a>, path: &str, size: u16, thickness: u16, color: Color) -> Result<(), String> {
+        self.outline = Some((fonts.outlined(path, size, thickness)?, thickness, color));
+        self.render()
+    }
+    pub fn shadow(&mut self, shadow: Option<(u32, Color)>) -> Result<(), String> {
+        if self.shadow == shadow {
+            return Ok(())
+        }
+        self.shadow = shadow;
+        self.render()
+    }
+    //the byte ranges of the rows in `content`, the spaces the rows were broken at are left out
+    fn rows(&self) -> Result<Vec<Range<usize>>, String> {
+        let mut rows = vec![];
+        let mut at = 0;
+        for paragraph in self.content.split('\n') {
+            let Some(width) = self.width else {
+                rows.push(at..at + paragraph.len());
+                at += paragraph.len() + 1;
+                continue
+            };
+            let (mut start, mut end) = (at, at);
+            for word in paragraph.split(' ') {
+                let word_end = at + word.len();
+                //a word wider than the width gets a row of its own
+                if end > start && self.width_of(&self.content[start..word_end])? > width {
+                    rows.push(start..end);
+                    start = at;
+                }
+                end = word_end;
+                at = word_end + 1;
+            }
+            rows.push(start..end);
+        }
+        Ok(rows)
+    }
+    fn width_of(&self, text: &str) -> Result<u32, String> {
+        if text.is_empty() {
+            return Ok(0)
+        }
+        Ok(self.font.size_of(text).map_err(|e| e.to_string())?.0)
+    }
+    //the visible parts of a row in the same color, as byte ranges in `content`
+    fn segments(&self, row: &Range<usize>) -> Vec<(Range<usize>, Color)> {
+        let first = self.content[..row.start].chars().count();
+        let mut segments: Vec<(Range<usize>, Color)> = vec![];
+        for (n, (i, c)) in self.content[row.clone()].char_indices().enumerate() {
+            let index = first + n;
+            if self.visible.is_some_and(|visible| index >= visible) {
+                break
+            }
+            let color = self.spans.iter()
+                .rev()
+                .find(|(range, _)| range.contains(&index))
+                .map_or(self.color, |(_, color)| *color);
+            let (start, end) = (row.start + i, row.start + i + c.len_utf8());
+            match segments.last_mut() {
+                Some((range, last)) if *last == color => range.end = end,
+                _ => segments.push((start..end, color)),
+            }
+        }
+        segments
+    }
+    fn render(&mut self) -> Result<(), String> {
+        let rows = self.rows()?;
+        let widths = rows.iter().map(|row| self.width_of(&self.content[row.clone()])).collect::<Result<Vec<u32>, String>>()?;
+        let inner_w = self.width.unwrap_or_else(|| widths.iter().copied().max().unwrap_or(0));
+        let spacing = self.font.recommended_line_spacing().max(0) as u32;
+        let pad = self.outline.as_ref().map_or(0, |(_, thickness, _)| *thickness as u32);
+        let distance = self.shadow.map_or(0, |(distance, _)| distance);
+        let (w, h) = (inner_w + 2 * pad + distance, rows.len() as u32 * spacing + 2 * pad + distance);
+
+
+        //keeping the aligned edge where it was, the size is kept up to date even if nothing is drawn
+        let x = match self.align {
+            Align::Left => self.dst.x(),
+            Align::Center => self.dst.center().x() - w as i32 / 2,
+            Align::Right => self.dst.right() - w as i32,
+        };
+        self.dst = Rect::new(x, self.dst.y(), w, h);
+
+        let segments: Vec<_> = rows.iter().map(|row| self.segments(row)).collect();
+        if w == 0 || h == 0 || segments.iter().all(|row| row.is_empty()) {
+            self.current = None;
+            return Ok(())
+        }
+
+        let mut surface = Surface::new(w, h, PixelFormatEnum::ARGB8888)?;
+        //transparent in the text's color, so the edges of the glyphs don't get darker when blended onto it
+        surface.fill_rect(None, Color::RGBA(self.color.r, self.color.g, self.color.b, 0))?;
+        for (i, (row, segments)) in rows.iter().zip(&segments).enumerate() {
+            let x = pad as i32 + match self.align {
+                Align::Left => 0,
+                Align::Center => inner_w.saturating_sub(widths[i]) as i32 / 2,
+                Align::Right => inner_w.saturating_sub(widths[i]) as i32,
+            };
+            let y = (pad + i as u32 * spacing) as i32;
+            let mut placed = vec![];
+            for (range, color) in segments {
+                let left = x + self.width_of(&self.content[row.start..range.start])? as i32;
+                placed.push((&self.content[range.clone()], *color, left));
+            }
+            //the shadows under the outlines under the text
+            if let Some((distance, color)) = self.shadow {
+                for (text, _, left) in &placed {
+                    draw(&self.font, text, color, left + distance as i32, y + distance as i32, &mut surface)?;
+                }
+            }
+            if let Some((font, thickness, color)) = &self.outline {
+                for (text, _, left) in &placed {
+                    draw(font, text, *color, left - *thickness as i32, y - *thickness as i32, &mut surface)?;
+                }
+            }
+            for (text, color, left) in &placed {
+                draw(&self.font, text, *color, *left, y, &mut surface)?;
+            }
+        }
+        self.current = Some(self.loader.create_texture_from_surface(&surface).map_err(|e| e.to_string())?);
+        Ok(())
+    }
+    pub fn present(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        if let Some(texture) = &self.current {
+            canvas.copy(texture, None, self.dst)?;
+        }
+        Ok(())
+    }
+    pub fn center(&mut self, display: &Display) {
+        let corner = display.center(self.dst.width(), self.dst.height());
+        self.dst.reposition(corner);
+    }
+}
+
+fn draw(font: &Font, text: &str, color: Color, x: i32, y: i32, target: &mut Surface) -> Result<(), String> {
+    let surface = font.render(text).blended(color).map_err(|e| e.to_string())?;
+    surface.blit(None, target, Rect::new(x, y, surface.width(), surface.height()))?;
+    Ok(())
+}
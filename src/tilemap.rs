@@ -0,0 +1,389 @@
+use std::{collections::HashMap, fs, path::Path, rc::Rc};
+use quick_xml::events::{BytesStart, Event};
+use sdl2::{rect::{Point, Rect}, render::{Texture, WindowCanvas}};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{assets::TextureCache, ComplexHitbox};
+
+//the parts of a Tiled map file the game uses, read from .tmj (JSON) or .tmx (XML)
+#[derive(Deserialize, Default)]
+struct TiledMap {
+    width: u32, //in tiles
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+    #[serde(default)]
+    tilesets: Vec<TiledTileset>,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TiledLayer {
+    Tilelayer {
+        #[serde(default)]
+        name: String,
+        data: Vec<u32>, //global tile ids row by row, 0 is empty (csv encoding only)
+        #[serde(default = "yes")]
+        visible: bool,
+    },
+    Objectgroup {
+        #[serde(default)]
+        objects: Vec<TiledObject>,
+    },
+    #[serde(other)]
+    Other, //image and group layers aren't used
+}
+
+#[derive(Deserialize, Default)]
+struct TiledTileset {
+    firstgid: u32,
+    source: Option<String>, //external tilesets aren't supported, embed them in the map
+    #[serde(default)]
+    image: String, //relative to the map file
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    columns: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+}
+
+#[derive(Deserialize, Default)]
+struct TiledObject {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type", alias = "class", default)]
+    kind: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32, //0 for point objects
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    value: Value,
+}
+
+fn yes() -> bool {
+    true
+}
+
+//an object from an object layer, e.g. a collision rect, a spawn point or an interaction zone
+pub struct MapObject {
+    pub name: String,
+    pub kind: String, //the object's type (class) in Tiled
+    pub area: Rect, //in the world's coordinates, 0x0 for points
+    properties: HashMap<String, Value>,
+}
+impl MapObject {
+    pub fn center(&self) -> Point {
+        Point::new(self.area.x() + self.area.width() as i32 / 2, self.area.y() + self.area.height() as i32 / 2)
+    }
+    pub fn number(&self, name: &str) -> Option<f64> {
+        self.properties.get(name)?.as_f64()
+    }
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.properties.get(name)?.as_str()
+    }
+}
+
+struct Tileset<'a> {
+    first: u32, //the global id of the first tile
+    sheet: Rc<Texture<'a>>,
+    columns: u32,
+    tile_w: u32,
+    tile_h: u32,
+    margin: u32,
+    spacing: u32,
+}
+impl Tileset<'_> {
+    fn src(&self, id: u32) -> Rect {
+        let local = id - self.first;
+        let (col, row) = (local % self.columns, local / self.columns);
+        Rect::new(
+            (self.margin + col * (self.tile_w + self.spacing)) as i32,
+            (self.margin + row * (self.tile_h + self.spacing)) as i32,
+            self.tile_w,
+            self.tile_h)
+    }
+}
+
+//a map made in Tiled, drawn tile by tile with its top left corner at the world's origin
+pub struct TileMap<'a> {
+    cols: u32,
+    rows: u32,
+    tile_w: u32, //the size of a tile on the screen, after scaling
+    tile_h: u32,
+    scale: f32, //the map's "scale" property
+    tilesets: Vec<Tileset<'a>>, //ordered by first id
+    layers: Vec<Vec<u32>>, //the visible tile layers, bottom first
+    pub objects: Vec<MapObject>,
+}
+impl<'a> TileMap<'a> {
+    pub fn load(path: &str, textures: &TextureCache<'a>) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let file = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("tmj") | Some("json") => serde_json::from_str(&content).map_err(|e| e.to_string()),
+            Some("tmx") => parse_tmx(&content),
+            _ => Err("not a Tiled map, expected .tmj or .tmx".to_string())
+        }.map_err(|e| format!("{}: {}", path, e))?;
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        TileMap::from(file, dir, textures).map_err(|e| format!("{}: {}", path, e))
+    }
+    fn from(file: TiledMap, dir: &Path, textures: &TextureCache<'a>) -> Result<Self, String> {
+        let scale = file.properties.iter()
+            .find(|property| property.name == "scale")
+            .and_then(|property| property.value.as_f64())
+            .unwrap_or(1.0) as f32;
+
+        let mut tilesets = vec![];
+        for tileset in file.tilesets {
+            if let Some(source) = tileset.source {
+                return Err(format!("external tileset '{}', embed it in the map", source));
+            }
+            if tileset.columns == 0 {
+                return Err(format!("tileset '{}' has no columns", tileset.image));
+            }
+            let image = dir.join(&tileset.image);
+            tilesets.push(Tileset {
+                first: tileset.firstgid,
+                sheet: textures.load(&image.to_string_lossy())?,
+                columns: tileset.columns,
+                tile_w: tileset.tilewidth,
+                tile_h: tileset.tileheight,
+                margin: tileset.margin,
+                spacing: tileset.spacing,
+            });
+        }
+        tilesets.sort_by_key(|tileset| tileset.first);
+
+        let mut layers = vec![];
+        let mut objects = vec![];
+        for layer in file.layers {
+            match layer {
+                TiledLayer::Tilelayer { name, data, visible } => {
+                    if data.len() != (file.width * file.height) as usize {
+                        return Err(format!("layer '{}' has {} tiles instead of {}x{}", name, data.len(), file.width, file.height));
+                    }
+                    if visible {
+                        layers.push(data);
+                    }
+                }
+                TiledLayer::Objectgroup { objects: group } => {
+                    for object in group {
+                        let area = Rect::new(
+                            (object.x * scale).round() as i32,
+                            (object.y * scale).round() as i32,
+                            (object.width * scale).round() as u32,
+                            (object.height * scale).round() as u32);
+                        objects.push(MapObject {
+                            name: object.name,
+                            kind: object.kind,
+                            area,
+                            properties: object.properties.into_iter().map(|property| (property.name, property.value)).collect(),
+                        });
+                    }
+                }
+                TiledLayer::Other => {}
+            }
+        }
+
+        Ok(TileMap {
+            cols: file.width,
+            rows: file.height,
+            tile_w: (file.tilewidth as f32 * scale).round() as u32,
+            tile_h: (file.tileheight as f32 * scale).round() as u32,
+            scale,
+            tilesets,
+            layers,
+            objects,
+        })
+    }
+    //the whole map in the world's coordinates
+    pub fn area(&self) -> Rect {
+        Rect::new(0, 0, self.cols * self.tile_w, self.rows * self.tile_h)
+    }
+    //a ComplexHitbox made of the objects with the "collision" type
+    pub fn hitboxes(&self) -> ComplexHitbox {
+        let mut map = ComplexHitbox::new(self.area());
+        for object in self.objects.iter().filter(|object| object.kind == "collision") {
+            map.add_hitbox(object.area);
+        }
+        map
+    }
+    fn tileset(&self, id: u32) -> Option<&Tileset<'a>> {
+        self.tilesets.iter().rev().find(|tileset| tileset.first <= id)
+    }
+    //drawing only the tiles inside `view` (the visible part of the world)
+    pub fn present(&self, canvas: &mut WindowCanvas, view: Rect, world_x: i32, world_y: i32) -> Result<(), String> {
+        let (tile_w, tile_h) = (self.tile_w as i32, self.tile_h as i32);
+        let first_col = view.left().div_euclid(tile_w).max(0);
+        let first_row = view.top().div_euclid(tile_h).max(0);
+        let last_col = view.right().div_euclid(tile_w).min(self.cols as i32 - 1);
+        //tiles taller than the grid stick out upwards, so the row below the view can still show
+        let last_row = (view.bottom().div_euclid(tile_h) + 1).min(self.rows as i32 - 1);
+
+        for layer in &self.layers {
+            for row in first_row..=last_row {
+                for col in first_col..=last_col {
+                    //the top bits are Tiled's flip flags, flipped tiles are drawn unflipped
+                    let id = layer[(row * self.cols as i32 + col) as usize] & 0x0FFF_FFFF;
+                    if id == 0 {
+                        continue
+                    }
+                    let Some(tileset) = self.tileset(id) else {continue};
+                    //tiles bigger than the grid are aligned to the bottom left of their cell, like in Tiled
+                    let w = (tileset.tile_w as f32 * self.scale).round() as u32;
+                    let h = (tileset.tile_h as f32 * self.scale).round() as u32;
+                    let dst = Rect::new(
+                        col * tile_w + world_x,
+                        (row + 1) * tile_h - h as i32 + world_y,
+                        w, h);
+                    canvas.copy(&tileset.sheet, tileset.src(id), dst)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+//the attributes of an xml tag by name
+fn attributes(tag: &BytesStart) -> Result<HashMap<String, String>, String> {
+    let mut attributes = HashMap::new();
+    for attribute in tag.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).to_string();
+        let value = attribute.unescape_value().map_err(|e| e.to_string())?.to_string();
+        attributes.insert(key, value);
+    }
+    Ok(attributes)
+}
+fn number<T: std::str::FromStr + Default>(attributes: &HashMap<String, String>, key: &str) -> Result<T, String> {
+    match attributes.get(key) {
+        Some(value) => value.parse().map_err(|_| format!("'{}' is not a valid {}", value, key)),
+        None => Ok(T::default())
+    }
+}
+//reading a .tmx file into the same structure serde makes of a .tmj
+fn parse_tmx(content: &str) -> Result<TiledMap, String> {
+    let mut reader = quick_xml::Reader::from_str(content);
+    reader.trim_text(true);
+    let mut map = TiledMap::default();
+    let mut parents: Vec<String> = vec![]; //the tags the reader is inside of
+
+    loop {
+        let event = reader.read_event().map_err(|e| e.to_string())?;
+        let (tag, empty) = match &event {
+            Event::Start(tag) => (tag, false),
+            Event::Empty(tag) => (tag, true),
+            Event::Text(text) => {
+                if parents.last().map(String::as_str) == Some("data") {
+                    let text = text.unescape().map_err(|e| e.to_string())?;
+                    if let Some(TiledLayer::Tilelayer { data, .. }) = map.layers.last_mut() {
+                        for id in text.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                            data.push(id.parse().map_err(|_| format!("'{}' is not a tile id", id))?);
+                        }
+                    }
+                }
+                continue
+            }
+            Event::End(_) => {
+                parents.pop();
+                continue
+            }
+            Event::Eof => break,
+            _ => continue
+        };
+        let name = String::from_utf8_lossy(tag.name().as_ref()).to_string();
+        let attrs = attributes(tag)?;
+        let parent = parents.last().map(String::as_str);
+        match (name.as_str(), parent) {
+            ("map", _) => {
+                map.width = number(&attrs, "width")?;
+                map.height = number(&attrs, "height")?;
+                map.tilewidth = number(&attrs, "tilewidth")?;
+                map.tileheight = number(&attrs, "tileheight")?;
+            }
+            ("tileset", _) => map.tilesets.push(TiledTileset {
+                firstgid: number(&attrs, "firstgid")?,
+                source: attrs.get("source").cloned(),
+                tilewidth: number(&attrs, "tilewidth")?,
+                tileheight: number(&attrs, "tileheight")?,
+                columns: number(&attrs, "columns")?,
+                margin: number(&attrs, "margin")?,
+                spacing: number(&attrs, "spacing")?,
+                ..Default::default()
+            }),
+            ("image", Some("tileset")) => {
+                if let Some(tileset) = map.tilesets.last_mut() {
+                    tileset.image = attrs.get("source").cloned().unwrap_or_default();
+                }
+            }
+            ("layer", _) => map.layers.push(TiledLayer::Tilelayer {
+                name: attrs.get("name").cloned().unwrap_or_default(),
+                data: vec![],
+                visible: attrs.get("visible").map_or(true, |visible| visible != "0"),
+            }),
+            ("data", _) => {
+                if attrs.get("encoding").map(String::as_str) != Some("csv") {
+                    return Err("only csv encoded tile layers are supported".to_string());
+                }
+            }
+            ("objectgroup", _) => map.layers.push(TiledLayer::Objectgroup { objects: vec![] }),
+            ("object", _) => {
+                if let Some(TiledLayer::Objectgroup { objects, .. }) = map.layers.last_mut() {
+                    objects.push(TiledObject {
+                        name: attrs.get("name").cloned().unwrap_or_default(),
+                        kind: attrs.get("type").or(attrs.get("class")).cloned().unwrap_or_default(),
+                        x: number(&attrs, "x")?,
+                        y: number(&attrs, "y")?,
+                        width: number(&attrs, "width")?,
+                        height: number(&attrs, "height")?,
+                        properties: vec![],
+                    });
+                }
+            }
+            ("property", Some("properties")) => {
+                let raw = attrs.get("value").cloned().unwrap_or_default();
+                let value = match attrs.get("type").map(String::as_str) {
+                    Some("int") | Some("float") => raw.parse::<f64>().map(Value::from).map_err(|_| format!("'{}' is not a number", raw))?,
+                    Some("bool") => Value::Bool(raw == "true"),
+                    _ => Value::String(raw)
+                };
+                let property = TiledProperty { name: attrs.get("name").cloned().unwrap_or_default(), value };
+                //the tag owning the <properties>
+                match parents.iter().rev().nth(1).map(String::as_str) {
+                    Some("map") => map.properties.push(property),
+                    Some("object") => {
+                        if let Some(TiledLayer::Objectgroup { objects, .. }) = map.layers.last_mut() {
+                            if let Some(object) = objects.last_mut() {
+                                object.properties.push(property);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        if !empty {
+            parents.push(name);
+        }
+    }
+    Ok(map)
+}
@@ -0,0 +1,57 @@
+use std::{thread::sleep, time::{Duration, Instant}};
+
+//fixed timestep clock: the simulation always advances in steps of `tick`,
+//no matter how long rendering a frame took
+pub struct Clock {
+    tick: Duration, //the simulated time of one update
+    accumulator: Duration, //real time not yet consumed by updates
+    last: Instant, //when the clock was last advanced
+    max_frame: Duration, //frames longer than this are clamped, so a hiccup doesn't cause a burst of updates
+}
+impl Clock {
+    pub fn new(tick_rate: u32) -> Self {
+        Clock {
+            tick: Duration::from_secs(1) / tick_rate,
+            accumulator: Duration::ZERO,
+            last: Instant::now(),
+            max_frame: Duration::from_millis(250),
+        }
+    }
+    //call once per frame, before running the updates
+    pub fn advance(&mut self) {
+        let now = Instant::now();
+        self.accumulator += (now - self.last).min(self.max_frame);
+        self.last = now;
+    }
+    //true while there is a whole tick left to simulate, e.g. `while clock.tick() { update(clock.dt()) }`
+    pub fn tick(&mut self) -> bool {
+        if self.accumulator >= self.tick {
+            self.accumulator -= self.tick;
+            return true
+        }
+        false
+    }
+    pub fn dt(&self) -> Duration {
+        self.tick
+    }
+    //how far the current frame is between the last and the next update (0.0 - 1.0)
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.tick.as_secs_f32()
+    }
+    //sleeping until the next frame is due instead of spinning, with no cap (0) only vsync holds the frames back
+    pub fn idle(&self, frame_cap: u32) {
+        if frame_cap == 0 {
+            return
+        }
+        let frame = Duration::from_secs(1) / frame_cap;
+        let elapsed = self.last.elapsed();
+        if elapsed < frame {
+            sleep(frame - elapsed);
+        }
+    }
+}
+
+//the distance travelled in `dt` at `speed` pixels per second
+pub fn step(speed: i32, dt: Duration) -> i32 {
+    (speed as f32 * dt.as_secs_f32()).round() as i32
+}
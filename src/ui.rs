@@ -0,0 +1,548 @@
+use std::time::Duration;
+use kira::sound::static_sound::{StaticSoundData, StaticSoundSettings};
+use sdl2::{
+    keyboard::Scancode,
+    mouse::MouseButton,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::WindowCanvas,
+};
+
+use crate::{
+    assets::FontCache, display::Display, input::{Action, Controls}, scenes::{Context, Input}, text::Text,
+};
+
+const FONT: &str = "assets/fonts/Aiden-v7DO.otf";
+//played quietly when the focus moves to another widget
+const FOCUS_SOUND: &str = "assets/sounds/tick.wav";
+//holding a direction moves the focus or a slider again after the delay, then once every interval
+const NAVIGATION_REPEAT: (Duration, Duration) = (Duration::from_millis(400), Duration::from_millis(120));
+const BOX: u32 = 50; //the size of a checkbox's box
+const GAP: i32 = 20; //between a box or a dropdown's frame and the text
+const ARROW: i32 = 24; //the width of a dropdown's arrow
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+enum State {
+    Normal,
+    Focused, //selected with the keys or the mouse
+    Pressed, //the mouse button or the interact key is held on it
+    Disabled,
+}
+impl State {
+    fn color(self) -> Color {
+        match self {
+            State::Normal => Color::RGB(255, 255, 255),
+            State::Focused => Color::RGB(255, 220, 120),
+            State::Pressed => Color::RGB(200, 160, 60),
+            State::Disabled => Color::RGB(110, 110, 110),
+        }
+    }
+}
+
+//what the player did with a widget, with the widget's command
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Response<T> {
+    Pressed(T), //a button
+    Changed(T, f64), //a slider's new value
+    Toggled(T, bool), //a checkbox's new state
+    Selected(T, usize), //the index of a dropdown's new option
+}
+
+enum Kind<'a> {
+    Label,
+    Button,
+    Slider {value: f64, step: f64}, //the value is between 0.0 and 1.0
+    Checkbox {checked: bool},
+    Dropdown {options: Vec<Text<'a>>, selected: usize, highlighted: usize, open: bool},
+}
+
+pub struct Widget<'a, T> {
+    kind: Kind<'a>,
+    label: Text<'a>, //a dropdown shows the selected option in it
+    command: Option<T>, //labels have none and can't be focused
+    min: (u32, u32), //the least room it takes, the label can make it bigger
+    area: Rect, //where the layout put it
+    pub enabled: bool,
+    pub repeat: Option<(Duration, Duration)>, //a button firing again while held, after the first and between the rest
+}
+impl<'a, T: Copy + PartialEq> Widget<'a, T> {
+    fn new(fonts: &FontCache<'a>, content: &str, size: u16, kind: Kind<'a>, command: Option<T>, min: (u32, u32)) -> Result<Self, String> {
+        let mut label = Text::new(fonts, FONT, size, State::Normal.color(), Point::new(0, 0))?;
+        label.set(content)?;
+        Ok(Widget { kind, label, command, min, area: Rect::new(0, 0, 1, 1), enabled: true, repeat: None })
+    }
+    pub fn label(fonts: &FontCache<'a>, content: &str, size: u16) -> Result<Self, String> {
+        Widget::new(fonts, content, size, Kind::Label, None, (0, 0))
+    }
+    pub fn button(fonts: &FontCache<'a>, content: &str, command: T, min: (u32, u32)) -> Result<Self, String> {
+        Widget::new(fonts, content, 70, Kind::Button, Some(command), min)
+    }
+    //moving by `step` with the keys, the mouse can drag it anywhere
+    pub fn slider(fonts: &FontCache<'a>, command: T, value: f64, step: f64, width: u32) -> Result<Self, String> {
+        if step.is_nan() || step <= 0.0 {
+            return Err(format!("a slider needs a positive step, not {}", step))
+        }
+        let kind = Kind::Slider {value: value.clamp(0.0, 1.0), step};
+        Widget::new(fonts, "", 70, kind, Some(command), (width, 60))
+    }
+    pub fn checkbox(fonts: &FontCache<'a>, content: &str, command: T, checked: bool) -> Result<Self, String> {
+        Widget::new(fonts, content, 60, Kind::Checkbox {checked}, Some(command), (0, BOX))
+    }
+    pub fn dropdown(fonts: &FontCache<'a>, options: &[&str], command: T, selected: usize) -> Result<Self, String> {
+        if options.is_empty() {
+            return Err("a dropdown needs at least one option".to_string())
+        }
+        let mut texts = vec![];
+        for option in options {
+            let mut text = Text::new(fonts, FONT, 60, State::Normal.color(), Point::new(0, 0))?;
+            text.set(option)?;
+            texts.push(text);
+        }
+        let selected = selected.min(options.len() - 1);
+        let kind = Kind::Dropdown {options: texts, selected, highlighted: selected, open: false};
+        Widget::new(fonts, options[selected], 60, kind, Some(command), (0, 70))
+    }
+    //the layout has to be done again if the label got bigger
+    pub fn set_label(&mut self, content: &str) -> Result<(), String> {
+        self.label.set(content)
+    }
+    fn focusable(&self) -> bool {
+        self.enabled && self.command.is_some()
+    }
+    fn open(&self) -> bool {
+        matches!(self.kind, Kind::Dropdown {open: true, ..})
+    }
+    fn size(&self) -> (u32, u32) {
+        let (w, h) = if self.label.content().is_empty() {(0, 0)} else {self.label.dst.size()};
+        let (w, h) = match &self.kind {
+            Kind::Label | Kind::Button => (w, h),
+            Kind::Slider {..} => (0, 0),
+            Kind::Checkbox {..} => (BOX + GAP as u32 + w, h),
+            Kind::Dropdown {options, ..} => {
+                let widest = options.iter().map(|option| option.dst.width()).max().unwrap_or(0);
+                (widest + 3 * GAP as u32 + ARROW as u32, h)
+            }
+        };
+        (w.max(self.min.0), h.max(self.min.1))
+    }
+    //the row of a dropdown's option under it
+    fn row(&self, i: usize) -> Rect {
+        Rect::new(self.area.x(), self.area.bottom() + (i as u32 * self.area.height()) as i32, self.area.width(), self.area.height())
+    }
+    //the mouse went down on the widget, or keeps pressing it
+    fn click(&mut self, controls: &Controls, cursor: Point) -> Option<Response<T>> {
+        let command = self.command?;
+        let clicked = controls.mouse_pressed(MouseButton::Left);
+        match &mut self.kind {
+            Kind::Label => None,
+            Kind::Button => {
+                let fired = match self.repeat {
+                    Some((delay, interval)) => controls.mouse_repeated(MouseButton::Left, delay, interval),
+                    None => clicked,
+                };
+                fired.then_some(Response::Pressed(command))
+            }
+            //dragged for as long as the button is held
+            Kind::Slider {value, step} => {
+                let dragged = (cursor.x() - self.area.x()) as f64 / self.area.width() as f64;
+                let dragged = ((dragged / *step).round() * *step).clamp(0.0, 1.0);
+                if dragged == *value {
+                    return None
+                }
+                *value = dragged;
+                Some(Response::Changed(command, dragged))
+            }
+            Kind::Checkbox {checked} if clicked => {
+                *checked = !*checked;
+                Some(Response::Toggled(command, *checked))
+            }
+            Kind::Dropdown {selected, highlighted, open, ..} if clicked => {
+                *open = true;
+                *highlighted = *selected;
+                None
+            }
+            _ => None
+        }
+    }
+    //the keys used on the focused widget
+    fn keys(&mut self, controls: &Controls, confirm: bool) -> Option<Response<T>> {
+        let command = self.command?;
+        match &mut self.kind {
+            Kind::Label => None,
+            Kind::Button => {
+                let fired = match self.repeat {
+                    Some((delay, interval)) => confirm || controls.repeated(Action::Interact, delay, interval),
+                    None => confirm,
+                };
+                fired.then_some(Response::Pressed(command))
+            }
+            Kind::Slider {value, step} => {
+                let (delay, interval) = NAVIGATION_REPEAT;
+                let mut moved = *value;
+                if controls.repeated(Action::MoveLeft, delay, interval) {
+                    moved -= *step;
+                }
+                if controls.repeated(Action::MoveRight, delay, interval) {
+                    moved += *step;
+                }
+                //rounded to the step so adding it up doesn't drift
+                let moved = ((moved / *step).round() * *step).clamp(0.0, 1.0);
+                if moved == *value {
+                    return None
+                }
+                *value = moved;
+                Some(Response::Changed(command, moved))
+            }
+            Kind::Checkbox {checked} if confirm => {
+                *checked = !*checked;
+                Some(Response::Toggled(command, *checked))
+            }
+            Kind::Dropdown {selected, highlighted, open, ..} if confirm => {
+                *open = true;
+                *highlighted = *selected;
+                None
+            }
+            _ => None
+        }
+    }
+    //an open dropdown's list of options
+    fn choose(&mut self, controls: &Controls, cursor: Point, moved: bool, confirm: bool) -> Result<Option<Response<T>>, String> {
+        let Some(command) = self.command else {return Ok(None)};
+        let rows: Vec<Rect> = (0..self.options()).map(|i| self.row(i)).collect();
+        let Kind::Dropdown {options, selected, highlighted, open} = &mut self.kind else {return Ok(None)};
+        let count = options.len();
+        let hovered = rows.iter().position(|row| row.contains_point(cursor));
+        let clicked = controls.mouse_pressed(MouseButton::Left);
+
+        let (delay, interval) = NAVIGATION_REPEAT;
+        if moved {
+            *highlighted = hovered.unwrap_or(*highlighted);
+        }
+        if controls.repeated(Action::MoveUp, delay, interval) {
+            *highlighted = (*highlighted + count - 1) % count;
+        }
+        if controls.repeated(Action::MoveDown, delay, interval) {
+            *highlighted = (*highlighted + 1) % count;
+        }
+        if confirm || (clicked && hovered.is_some()) {
+            *selected = hovered.filter(|_| clicked).unwrap_or(*highlighted);
+            *open = false;
+            let chosen = *selected;
+            self.label.set(options[chosen].content())?;
+            return Ok(Some(Response::Selected(command, chosen)))
+        }
+        //clicking anywhere else closes it
+        if clicked || controls.pressed(Action::Pause) {
+            *open = false;
+        }
+        Ok(None)
+    }
+    fn options(&self) -> usize {
+        match &self.kind {
+            Kind::Dropdown {options, ..} => options.len(),
+            _ => 0
+        }
+    }
+    fn present(&mut self, canvas: &mut WindowCanvas, state: State) -> Result<(), String> {
+        let color = state.color();
+        self.label.set_color(color)?;
+        canvas.set_draw_color(color);
+        let (area, center) = (self.area, self.area.center());
+        let label_h = self.label.dst.height() as i32;
+        match &self.kind {
+            Kind::Label => self.label.dst.center_on(center),
+            //sinking a little while pressed
+            Kind::Button => self.label.dst.center_on(center.offset(0, if state == State::Pressed {4} else {0})),
+            Kind::Slider {value, ..} => {
+                let bar = Rect::new(area.x(), center.y() - 20, area.width(), 40);
+                canvas.draw_rect(bar)?;
+                let filled = (bar.width() as f64 * value) as u32;
+                if filled > 0 {
+                    canvas.fill_rect(Rect::new(bar.x(), bar.y(), filled, bar.height()))?;
+                }
+            }
+            Kind::Checkbox {checked} => {
+                let frame = Rect::new(area.x(), center.y() - BOX as i32 / 2, BOX, BOX);
+                canvas.draw_rect(frame)?;
+                if *checked {
+                    canvas.fill_rect(Rect::new(frame.x() + 10, frame.y() + 10, BOX - 20, BOX - 20))?;
+                }
+                self.label.dst.reposition((area.x() + BOX as i32 + GAP, center.y() - label_h / 2));
+            }
+            Kind::Dropdown {..} => {
+                canvas.draw_rect(area)?;
+                //an arrow pointing down on the right
+                let (x, y) = (area.right() - GAP - ARROW, center.y() - ARROW / 4);
+                for i in 0..ARROW / 2 {
+                    canvas.draw_line((x + i, y + i), (x + ARROW - i, y + i))?;
+                }
+                self.label.dst.reposition((area.x() + GAP, center.y() - label_h / 2));
+            }
+        }
+        self.label.present(canvas)
+    }
+    //drawn after every other widget so the list covers them
+    fn present_options(&mut self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        let rows: Vec<Rect> = (0..self.options()).map(|i| self.row(i)).collect();
+        let Kind::Dropdown {options, highlighted, open: true, ..} = &mut self.kind else {return Ok(())};
+        for (i, (option, row)) in options.iter_mut().zip(rows).enumerate() {
+            let state = if i == *highlighted {State::Focused} else {State::Normal};
+            canvas.set_draw_color(Color::RGB(0, 0, 0));
+            canvas.fill_rect(row)?;
+            canvas.set_draw_color(state.color());
+            canvas.draw_rect(row)?;
+            option.set_color(state.color())?;
+            option.dst.reposition((row.x() + GAP, row.center().y() - option.dst.height() as i32 / 2));
+            option.present(canvas)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Flow {
+    Column, //the children under each other
+    Row, //the children next to each other
+}
+
+pub enum Node<'a, T> {
+    Widget(Widget<'a, T>),
+    Container(Container<'a, T>),
+}
+impl<'a, T> From<Widget<'a, T>> for Node<'a, T> {
+    fn from(widget: Widget<'a, T>) -> Self {
+        Node::Widget(widget)
+    }
+}
+impl<'a, T> From<Container<'a, T>> for Node<'a, T> {
+    fn from(container: Container<'a, T>) -> Self {
+        Node::Container(container)
+    }
+}
+impl<'a, T: Copy + PartialEq> Node<'a, T> {
+    fn size(&self) -> (u32, u32) {
+        match self {
+            Node::Widget(widget) => widget.size(),
+            Node::Container(container) => container.size(),
+        }
+    }
+    fn place(&mut self, corner: Point) {
+        match self {
+            Node::Widget(widget) => widget.area = Rect::new(corner.x(), corner.y(), widget.size().0, widget.size().1),
+            Node::Container(container) => container.place(corner),
+        }
+    }
+}
+
+//lays its children out in a column or a row, centering them across it
+pub struct Container<'a, T> {
+    flow: Flow,
+    padding: u32, //around the children
+    spacing: u32, //between two children
+    anchor: (f32, f32), //where it goes in the room around it on the screen, (0.0, 0.0) is the top left corner, (0.5, 0.5) the middle
+    offset: (i32, i32), //from the anchor
+    children: Vec<Node<'a, T>>,
+}
+impl<'a, T: Copy + PartialEq> Container<'a, T> {
+    pub fn new(flow: Flow) -> Self {
+        Container {flow, padding: 0, spacing: 0, anchor: (0.5, 0.5), offset: (0, 0), children: vec![]}
+    }
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+    pub fn spacing(mut self, spacing: u32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+    //only used by the outermost container, the rest are placed by their parents
+    pub fn anchor(mut self, anchor: (f32, f32), offset: (i32, i32)) -> Self {
+        self.anchor = anchor;
+        self.offset = offset;
+        self
+    }
+    pub fn with(mut self, child: impl Into<Node<'a, T>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+    fn size(&self) -> (u32, u32) {
+        let sizes: Vec<(u32, u32)> = self.children.iter().map(Node::size).collect();
+        let gaps = self.spacing * sizes.len().saturating_sub(1) as u32;
+        let widest = sizes.iter().map(|size| size.0).max().unwrap_or(0);
+        let highest = sizes.iter().map(|size| size.1).max().unwrap_or(0);
+        let (w, h) = match self.flow {
+            Flow::Column => (widest, sizes.iter().map(|size| size.1).sum::<u32>() + gaps),
+            Flow::Row => (sizes.iter().map(|size| size.0).sum::<u32>() + gaps, highest),
+        };
+        (w + 2 * self.padding, h + 2 * self.padding)
+    }
+    fn place(&mut self, corner: Point) {
+        let (w, h) = self.size();
+        let (inner_w, inner_h) = (w - 2 * self.padding, h - 2 * self.padding);
+        let (mut x, mut y) = (corner.x() + self.padding as i32, corner.y() + self.padding as i32);
+        for child in &mut self.children {
+            let (child_w, child_h) = child.size();
+            match self.flow {
+                Flow::Column => {
+                    child.place(Point::new(x + (inner_w - child_w) as i32 / 2, y));
+                    y += (child_h + self.spacing) as i32;
+                }
+                Flow::Row => {
+                    child.place(Point::new(x, y + (inner_h - child_h) as i32 / 2));
+                    x += (child_w + self.spacing) as i32;
+                }
+            }
+        }
+    }
+    //every widget inside, in the order the focus goes through them
+    fn widgets(&mut self) -> Vec<&mut Widget<'a, T>> {
+        let mut widgets = vec![];
+        for child in &mut self.children {
+            match child {
+                Node::Widget(widget) => widgets.push(widget),
+                Node::Container(container) => widgets.extend(container.widgets()),
+            }
+        }
+        widgets
+    }
+}
+
+//a tree of widgets that can be used with the mouse, the keys or a controller
+pub struct Ui<'a, T> {
+    root: Container<'a, T>,
+    focus: Option<usize>, //the focused widget's index in `Container::widgets`
+    grabbed: Option<usize>, //the widget the left mouse button went down on, while it's held
+    held: bool, //the focused widget is being pressed
+    cursor: Point, //the mouse in the last tick, the focus only follows it when it moves
+    settling: bool, //nothing is pressed until the confirming keys and the mouse are let go, see `reset`
+    sound: StaticSoundData,
+}
+impl<'a, T: Copy + PartialEq> Ui<'a, T> {
+    pub fn new(root: Container<'a, T>, ctx: &Context<'a>) -> Result<Self, String> {
+        let settings = StaticSoundSettings::new().volume(0.3).track(&ctx.sfx);
+        let sound = StaticSoundData::from_file(FOCUS_SOUND, settings).map_err(|e| e.to_string())?;
+        let mut ui = Ui {root, focus: None, grabbed: None, held: false, cursor: Point::new(0, 0), settling: true, sound};
+        ui.layout(&ctx.display);
+        Ok(ui)
+    }
+    pub fn layout(&mut self, display: &Display) {
+        let (w, h) = self.root.size();
+        let (free_w, free_h) = (display.width as f32 - w as f32, display.height as f32 - h as f32);
+        let (anchor_x, anchor_y) = self.root.anchor;
+        let (x, y) = self.root.offset;
+        self.root.place(Point::new((free_w * anchor_x) as i32 + x, (free_h * anchor_y) as i32 + y));
+    }
+    pub fn widget(&mut self, command: T) -> Option<&mut Widget<'a, T>> {
+        self.root.widgets().into_iter().find(|widget| widget.command == Some(command))
+    }
+    //forgetting the presses that started while another scene was on top, so they don't press a widget here
+    pub fn reset(&mut self) {
+        self.grabbed = None;
+        self.held = false;
+        self.settling = true;
+    }
+    //a dropdown is open, it uses the pause key to close
+    pub fn open(&mut self) -> bool {
+        self.root.widgets().iter().any(|widget| widget.open())
+    }
+    pub fn update(&mut self, ctx: &mut Context<'a>, input: &Input) -> Result<Option<Response<T>>, String> {
+        let focus = self.focus;
+        let response = self.handle(&ctx.controls, input)?;
+        if self.focus.is_some() && self.focus != focus {
+            ctx.audio.play(self.sound.clone()).map_err(|e| e.to_string())?;
+        }
+        Ok(response)
+    }
+    fn handle(&mut self, controls: &Controls, input: &Input) -> Result<Option<Response<T>>, String> {
+        let moved = input.cursor != self.cursor;
+        self.cursor = input.cursor;
+        if self.settling {
+            self.settling = controls.held(Action::Interact) || input.keyboard.is_scancode_pressed(Scancode::Return) || input.mouse.left();
+            if self.settling {
+                return Ok(None)
+            }
+        }
+        let confirm = controls.pressed(Action::Interact) || controls.key_pressed(Scancode::Return);
+        let mut widgets = self.root.widgets();
+        if self.focus.is_some_and(|i| !widgets[i].focusable()) {
+            self.focus = None;
+        }
+        if !input.mouse.left() {
+            self.grabbed = None;
+        }
+        self.held = match self.focus {
+            Some(i) => self.grabbed == Some(i) || controls.held(Action::Interact),
+            None => false
+        };
+
+        //an open dropdown takes every input until it's closed
+        if let Some(i) = self.focus.filter(|i| widgets[*i].open()) {
+            return widgets[i].choose(controls, input.cursor, moved, confirm)
+        }
+
+        //the focus follows the mouse, but only when it moves so it doesn't fight the keys
+        let hovered = widgets.iter().position(|widget| widget.focusable() && widget.area.contains_point(input.cursor));
+        if moved && hovered.is_some() {
+            self.focus = hovered;
+        }
+        //a focused slider uses left and right itself
+        let (delay, interval) = NAVIGATION_REPEAT;
+        let sliding = self.focus.is_some_and(|i| matches!(widgets[i].kind, Kind::Slider {..}));
+        let back = controls.repeated(Action::MoveUp, delay, interval) || (!sliding && controls.repeated(Action::MoveLeft, delay, interval));
+        let forward = controls.repeated(Action::MoveDown, delay, interval) || (!sliding && controls.repeated(Action::MoveRight, delay, interval));
+        if back != forward {
+            self.focus = step(&widgets, self.focus, forward);
+        }
+
+        if controls.mouse_pressed(MouseButton::Left) && hovered.is_some() {
+            self.grabbed = hovered;
+            self.focus = hovered;
+        }
+        //a slider only follows the mouse while grabbed, even when the mouse leaves it
+        let slider = |i: &usize| matches!(widgets[*i].kind, Kind::Slider {..});
+        let mouse = match self.grabbed.filter(slider) {
+            Some(i) => Some(i),
+            None => hovered.filter(|i| !slider(i)),
+        };
+        if let Some(response) = mouse.and_then(|i| widgets[i].click(controls, input.cursor)) {
+            return Ok(Some(response))
+        }
+        Ok(self.focus.and_then(|i| widgets[i].keys(controls, confirm)))
+    }
+    pub fn render(&mut self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        let (focus, held) = (self.focus, self.held);
+        let mut widgets = self.root.widgets();
+        for (i, widget) in widgets.iter_mut().enumerate() {
+            let state = if widget.command.is_none() {
+                State::Normal
+            } else if !widget.enabled {
+                State::Disabled
+            } else if focus == Some(i) && held {
+                State::Pressed
+            } else if focus == Some(i) {
+                State::Focused
+            } else {
+                State::Normal
+            };
+            widget.present(canvas, state)?;
+        }
+        if let Some(i) = focus {
+            widgets[i].present_options(canvas)?;
+        }
+        Ok(())
+    }
+}
+
+//the next or the previous focusable widget after `from`, going around at the ends
+fn step<T: Copy + PartialEq>(widgets: &[&mut Widget<T>], from: Option<usize>, forward: bool) -> Option<usize> {
+    let count = widgets.len();
+    if count == 0 {
+        return None
+    }
+    //starting from nothing, forward goes to the first one and back to the last one
+    let start = from.unwrap_or(if forward {count - 1} else {0});
+    (1..=count)
+        .map(|n| if forward {(start + n) % count} else {(start + count - n) % count})
+        .find(|i| widgets[*i].focusable())
+        .or(from)
+}